@@ -7,17 +7,29 @@
 #[macro_use]
 mod analyze;
 mod concurrent;
+mod configurations;
 mod declarative;
 mod design_unit;
+mod lint;
 mod lock;
 mod named_entity;
+mod processes;
 mod region;
 mod root;
 mod semantic;
 mod sequential;
+mod types;
 mod visibility;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::root::DesignRoot;
+pub use self::configurations::{BindingInfo, ConfigInfo};
+pub use self::lint::{Casing, CasingConvention, DeclarationKind, Lint};
+pub use self::named_entity::SymbolKind;
+pub use self::processes::{ProcessInfo, ResetInfo};
+pub use self::root::{
+    AnalysisEvent, DesignRoot, EditImpact, EnumInfo, MemberUsage, RenameConflict, RenamePreview,
+    Tooltip,
+};
+pub use self::types::TypeInfo;
@@ -4,8 +4,10 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
+use super::lint::Lint;
 use super::region::*;
 use super::root::*;
+use super::types::ExpressionType;
 use crate::ast::*;
 use crate::data::*;
 use fnv::FnvHashSet;
@@ -76,7 +78,7 @@ pub(super) struct AnalyzeContext<'a> {
     root: &'a DesignRoot,
 
     pub work_sym: Symbol,
-    std_sym: Symbol,
+    pub std_sym: Symbol,
     standard_sym: Symbol,
 
     // Record dependencies and sensitivies when
@@ -96,6 +98,15 @@ pub(super) struct AnalyzeContext<'a> {
     uses: RefCell<FnvHashSet<UnitId>>,
     missing_primary: RefCell<FnvHashSet<(Symbol, Symbol)>>,
     uses_library_all: RefCell<FnvHashSet<Symbol>>,
+
+    // Nested regions such as those of processes and subprograms are not part of the
+    // region of the design unit and are kept here to find local declarations after analysis
+    local_regions: RefCell<Vec<Region<'static>>>,
+
+    // The type of the expression at this position is determined in the region where the
+    // expression is analyzed
+    type_probe: Option<SrcPos>,
+    probed_type: RefCell<Option<ExpressionType>>,
 }
 
 impl<'a> AnalyzeContext<'a> {
@@ -109,9 +120,30 @@ impl<'a> AnalyzeContext<'a> {
             uses: RefCell::new(FnvHashSet::default()),
             missing_primary: RefCell::new(FnvHashSet::default()),
             uses_library_all: RefCell::new(FnvHashSet::default()),
+            local_regions: RefCell::new(Vec::new()),
+            type_probe: None,
+            probed_type: RefCell::new(None),
+        }
+    }
+
+    /// Determine the type of the expression at the position when it is analyzed
+    pub fn with_type_probe(self, pos: &SrcPos) -> AnalyzeContext<'a> {
+        AnalyzeContext {
+            type_probe: Some(pos.clone()),
+            ..self
+        }
+    }
+
+    pub fn probe_expression_type(&self, region: &Region<'_>, pos: &SrcPos, expr: &Expression) {
+        if self.type_probe.as_ref() == Some(pos) {
+            *self.probed_type.borrow_mut() = self.expression_pos_type(region, pos, expr);
         }
     }
 
+    pub fn probed_type(&self) -> Option<ExpressionType> {
+        self.probed_type.replace(None)
+    }
+
     pub fn work_library_name(&self) -> &Symbol {
         self.current_unit.library_name()
     }
@@ -124,10 +156,38 @@ impl<'a> AnalyzeContext<'a> {
         &self.current_unit
     }
 
+    /// The kind and the position of the name of the primary unit of the current design unit
+    pub fn current_primary_unit(&self) -> Option<(AnyKind, &SrcPos)> {
+        let unit = self
+            .root
+            .get_library_units(self.current_unit.library_name())?
+            .get(&UnitKey::Primary(self.current_unit.primary_name().clone()))?;
+        Some((unit.kind(), &unit.ident().pos))
+    }
+
     pub fn symbol_utf8(&self, name: &str) -> Symbol {
         self.root.symbol_utf8(name)
     }
 
+    /// Keep the declarations of an analyzed nested region
+    pub fn add_local_region(&self, region: &Region<'_>) {
+        self.local_regions
+            .borrow_mut()
+            .push(region.clone().without_parent());
+    }
+
+    pub fn take_local_regions(&self) -> Vec<Region<'static>> {
+        self.local_regions.replace(Vec::new())
+    }
+
+    pub fn is_lint_enabled(&self, lint: Lint) -> bool {
+        self.root.is_lint_enabled(lint)
+    }
+
+    pub fn enabled_lints(&self) -> impl Iterator<Item = &Lint> {
+        self.root.enabled_lints()
+    }
+
     fn make_use_of(&self, use_pos: Option<&SrcPos>, unit_id: &UnitId) -> FatalNullResult {
         // Check local cache before taking lock
         if self.uses.borrow_mut().insert(unit_id.clone()) {
@@ -159,6 +219,16 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    /// A library declared by a library clause although it does not exist
+    pub fn is_missing_library(&self, ent: &NamedEntity) -> bool {
+        match ent.kind() {
+            NamedEntityKind::Library => self
+                .get_library(ent.designator().expect_identifier())
+                .is_none(),
+            _ => false,
+        }
+    }
+
     pub fn use_all_in_library(
         &self,
         use_pos: &SrcPos,
@@ -321,6 +391,13 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    /// Analysis of the standard package or None when there is no standard library
+    pub fn standard_package_analysis(&self) -> Option<FatalResult<UnitReadGuard<'a>>> {
+        let unit =
+            self.get_primary_unit_kind(&self.std_sym, &self.standard_sym, PrimaryKind::Package)?;
+        Some(self.get_analysis(None, unit))
+    }
+
     pub fn get_primary_analysis(
         &self,
         use_pos: &SrcPos,
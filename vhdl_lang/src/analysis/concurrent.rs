@@ -11,7 +11,10 @@ use super::*;
 use crate::ast::*;
 use crate::data::*;
 use analyze::*;
+use lint::Lint;
 use region::*;
+use semantic::{missing_library_clause_error, ResolvedName};
+use sequential::check_unreachable_alternatives;
 
 impl<'a> AnalyzeContext<'a> {
     pub fn analyze_concurrent_part(
@@ -27,6 +30,35 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// The statements of an entity must be passive, a concurrent procedure call
+    /// is only allowed when the called procedure drives no signals
+    pub fn analyze_entity_statement_part(
+        &self,
+        parent: &mut Region<'_>,
+        statements: &mut [LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalNullResult {
+        for statement in statements.iter_mut() {
+            if let ConcurrentStatement::ProcedureCall(ref mut pcall) = statement.statement {
+                if let Some(ref label) = statement.label {
+                    parent.add(label.clone(), NamedEntityKind::Label, diagnostics);
+                }
+                let call = &mut pcall.call;
+                let resolved = self.analyze_function_call(parent, call, diagnostics)?;
+                check_passive_procedure_call(
+                    &call.name.pos,
+                    call.parameters.len(),
+                    resolved,
+                    diagnostics,
+                );
+            } else {
+                self.analyze_concurrent_statement(parent, statement, diagnostics)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn analyze_concurrent_statement(
         &self,
         parent: &mut Region<'_>,
@@ -57,6 +89,7 @@ impl<'a> AnalyzeContext<'a> {
                 }
                 self.analyze_declarative_part(&mut region, &mut block.decl, diagnostics)?;
                 self.analyze_concurrent_part(&mut region, &mut block.statements, diagnostics)?;
+                self.add_local_region(&region);
             }
             ConcurrentStatement::Process(ref mut process) => {
                 let ProcessStatement {
@@ -78,6 +111,11 @@ impl<'a> AnalyzeContext<'a> {
                 let mut region = parent.nested();
                 self.analyze_declarative_part(&mut region, decl, diagnostics)?;
                 self.analyze_sequential_part(&mut region, statements, diagnostics)?;
+                self.add_local_region(&region);
+
+                if self.is_lint_enabled(Lint::VariableAsSignal) {
+                    self.lint_variable_as_signal(decl, statements, diagnostics);
+                }
             }
             ConcurrentStatement::ForGenerate(ref mut gen) => {
                 let ForGenerateStatement {
@@ -85,14 +123,15 @@ impl<'a> AnalyzeContext<'a> {
                     discrete_range,
                     body,
                 } = gen;
-                self.analyze_discrete_range(parent, discrete_range, diagnostics)?;
+                let subtype = self.analyze_parameter_range(parent, discrete_range, diagnostics)?;
                 let mut region = parent.nested();
                 region.add(
                     index_name.clone(),
-                    NamedEntityKind::Object(ObjectClass::Constant),
+                    NamedEntityKind::Object(ObjectClass::Constant, subtype, None),
                     diagnostics,
                 );
                 self.analyze_generate_body(&mut region, body, diagnostics)?;
+                self.add_local_region(&region);
             }
             ConcurrentStatement::IfGenerate(ref mut gen) => {
                 let Conditionals {
@@ -104,25 +143,35 @@ impl<'a> AnalyzeContext<'a> {
                     self.analyze_expression(parent, condition, diagnostics)?;
                     let mut region = parent.nested();
                     self.analyze_generate_body(&mut region, item, diagnostics)?;
+                    self.add_local_region(&region);
                 }
                 if let Some(ref mut else_item) = else_item {
                     let mut region = parent.nested();
                     self.analyze_generate_body(&mut region, else_item, diagnostics)?;
+                    self.add_local_region(&region);
                 }
             }
             ConcurrentStatement::CaseGenerate(ref mut gen) => {
                 for alternative in gen.alternatives.iter_mut() {
                     let mut region = parent.nested();
                     self.analyze_generate_body(&mut region, &mut alternative.item, diagnostics)?;
+                    self.add_local_region(&region);
                 }
+                check_unreachable_alternatives(&gen.alternatives, diagnostics);
             }
             ConcurrentStatement::Instance(ref mut instance) => {
                 self.analyze_instance(parent, instance, diagnostics)?;
             }
             ConcurrentStatement::Assignment(ref mut assign) => {
-                // @TODO more delaymechanism
-                let ConcurrentSignalAssignment { target, rhs, .. } = assign;
+                let ConcurrentSignalAssignment {
+                    target,
+                    delay_mechanism,
+                    rhs,
+                    ..
+                } = assign;
                 self.analyze_waveform_assignment(parent, target, rhs, diagnostics)?;
+                self.analyze_delay_mechanism(parent, delay_mechanism, rhs, diagnostics)?;
+                self.check_string_literal_target(parent, target, rhs, diagnostics);
             }
             ConcurrentStatement::ProcedureCall(ref mut pcall) => {
                 let ConcurrentProcedureCall {
@@ -144,9 +193,11 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_expression(parent, condition, diagnostics)?;
                 if let Some(expr) = report {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    self.check_report(parent, expr, diagnostics);
                 }
                 if let Some(expr) = severity {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    self.check_severity(parent, expr, diagnostics);
                 }
             }
         };
@@ -181,6 +232,7 @@ impl<'a> AnalyzeContext<'a> {
         instance: &mut InstantiationStatement,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
+        let mut entity = None;
         match instance.unit {
             // @TODO architecture
             InstantiatedUnit::Entity(ref mut entity_name, ..) => {
@@ -192,10 +244,11 @@ impl<'a> AnalyzeContext<'a> {
                     }
                 }
 
-                if let Err(err) =
-                    self.resolve_non_overloaded(parent, entity_name, &is_entity, "entity")
-                {
-                    err.add_to(diagnostics)?;
+                if self.check_library_prefix(parent, entity_name, diagnostics) {
+                    match self.resolve_non_overloaded(parent, entity_name, &is_entity, "entity") {
+                        Ok(ent) => entity = Some(ent),
+                        Err(err) => err.add_to(diagnostics)?,
+                    }
                 }
             }
             InstantiatedUnit::Component(ref mut component_name) => {
@@ -236,6 +289,623 @@ impl<'a> AnalyzeContext<'a> {
         self.analyze_assoc_elems(parent, &mut instance.generic_map, diagnostics)?;
         self.analyze_assoc_elems(parent, &mut instance.port_map, diagnostics)?;
 
+        if let Some(ref entity) = entity {
+            if let NamedEntityKind::Entity(ref region) = entity.actual_kind() {
+                self.check_positional_associations(
+                    parent,
+                    entity,
+                    region,
+                    &instance.generic_map,
+                    false,
+                    diagnostics,
+                );
+                self.check_positional_associations(
+                    parent,
+                    entity,
+                    region,
+                    &instance.port_map,
+                    true,
+                    diagnostics,
+                );
+                self.check_port_widths(parent, region, &instance.port_map, diagnostics);
+                if self.is_lint_enabled(Lint::DirectionMismatch) {
+                    self.lint_port_directions(parent, region, &instance.port_map, diagnostics);
+                }
+            }
+        }
+
         Ok(())
     }
+
+    /// Distinguish a missing library from a library which is not made visible
+    /// when the prefix of a selected entity name is not declared
+    /// Returns false when the entity name shall not be resolved because of its prefix
+    /// A missing library of a library clause is only reported at the library clause
+    fn check_library_prefix(
+        &self,
+        parent: &Region<'_>,
+        entity_name: &WithPos<SelectedName>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> bool {
+        let prefix = if let SelectedName::Selected(ref prefix, _) = entity_name.item {
+            prefix
+        } else {
+            return true;
+        };
+
+        let designator = if let SelectedName::Designator(ref designator) = prefix.item {
+            &designator.item
+        } else {
+            return true;
+        };
+
+        let library_name = if let Designator::Identifier(ref sym) = designator {
+            sym
+        } else {
+            return true;
+        };
+
+        if let Ok(visible) = parent.lookup_within(&prefix.pos, designator) {
+            return match visible.into_non_overloaded() {
+                Ok(ent) => !self.is_missing_library(&ent),
+                Err(_) => true,
+            };
+        }
+
+        if self.get_library(library_name).is_some() {
+            diagnostics.push(missing_library_clause_error(&prefix.pos, library_name));
+        } else {
+            diagnostics.push(Diagnostic::error(
+                &prefix.pos,
+                format!("No such library '{}'", library_name),
+            ));
+        }
+        false
+    }
+
+    /// Check that there are no more positional actuals than ports or generics and that
+    /// each positional actual has the type of the port or generic declared at its position
+    /// Ports and generics of an unresolved subtype are counted but their actuals are not checked
+    /// Generic maps are not checked when there are generic subprograms or packages
+    fn check_positional_associations(
+        &self,
+        parent: &Region<'_>,
+        entity: &NamedEntity,
+        entity_region: &Region<'_>,
+        assocs: &[AssociationElement],
+        is_port_map: bool,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut formals = Vec::new();
+        for ent in entity_region.immediates() {
+            match ent.kind() {
+                NamedEntityKind::InterfaceObject(ref object)
+                    if (object.class == ObjectClass::Signal) == is_port_map =>
+                {
+                    formals.push(ent)
+                }
+                NamedEntityKind::InterfaceType if !is_port_map => formals.push(ent),
+                NamedEntityKind::Subprogram(..) | NamedEntityKind::LocalPackageInstance(..)
+                    if !is_port_map =>
+                {
+                    return;
+                }
+                _ => {}
+            }
+        }
+        formals.sort_by_key(|ent| ent.decl_pos().map(|pos| pos.start()));
+
+        let positional = assocs.iter().take_while(|assoc| assoc.formal.is_none());
+        for (idx, AssociationElement { actual, .. }) in positional.enumerate() {
+            let formal = if let Some(formal) = formals.get(idx) {
+                formal
+            } else {
+                diagnostics.push(Diagnostic::error(
+                    &actual.pos,
+                    format!(
+                        "Too many positional actuals, {} has {} {}",
+                        entity.describe(),
+                        formals.len(),
+                        if is_port_map { "ports" } else { "generics" }
+                    ),
+                ));
+                break;
+            };
+
+            if let (ActualPart::Expression(ref expr), Some(subtype)) =
+                (&actual.item, formal.object_subtype())
+            {
+                // The type of a port of a generic type is only known after elaboration
+                if subtype.base().base_type_class().is_some() {
+                    self.check_expression_pos_type(
+                        parent,
+                        &actual.pos,
+                        expr,
+                        subtype.base(),
+                        diagnostics,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Check that named port associations connect actuals of the same static width as the port
+    /// The width of the actual is known for names and concatenations of names
+    fn check_port_widths(
+        &self,
+        parent: &Region<'_>,
+        entity_region: &Region<'_>,
+        port_map: &[AssociationElement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for AssociationElement { formal, actual } in port_map.iter() {
+            let formal_name = if let Some(WithPos {
+                item: Name::Designator(ref designator),
+                ..
+            }) = formal
+            {
+                designator.designator()
+            } else {
+                continue;
+            };
+
+            let actual_expr = if let ActualPart::Expression(ref expr) = actual.item {
+                expr
+            } else {
+                continue;
+            };
+
+            let port_length = entity_region
+                .lookup_immediate(formal_name)
+                .and_then(|ents| ents.as_non_overloaded())
+                .and_then(|ent| ent.object_subtype())
+                .and_then(|subtype| subtype.length());
+
+            let actual_length = self.expression_length(parent, &actual.pos, actual_expr);
+
+            if let (Some(port_length), Some(actual_length)) = (port_length, actual_length) {
+                if port_length != actual_length {
+                    diagnostics.push(Diagnostic::error(
+                        &actual.pos,
+                        format!(
+                            "Expected width {} for port '{}', got width {}",
+                            port_length, formal_name, actual_length
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Check that signals of an unresolved subtype declared in the declarative part
+    /// are driven by at most one process or concurrent assignment
+    /// Assignments to elements or slices with static bounds only conflict when they overlap
+    /// Assignments to elements or slices without static bounds are not checked
+    pub fn check_multiple_drivers(
+        &self,
+        region: &Region<'_>,
+        decl: &[Declaration],
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let static_value =
+            |pos: &SrcPos, expr: &Expression| self.static_integer_value_at(region, pos, expr);
+        let mut drivers = Vec::new();
+        collect_drivers(statements, &static_value, &mut drivers);
+
+        for decl in decl.iter() {
+            let ident = if let Declaration::Object(ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ref ident,
+                ..
+            }) = decl
+            {
+                ident
+            } else {
+                continue;
+            };
+
+            let signal_drivers: Vec<_> = drivers
+                .iter()
+                .filter(|driver| driver.signal == ident.pos)
+                .collect();
+
+            let conflicting: Vec<_> = signal_drivers
+                .iter()
+                .enumerate()
+                .filter(|(i, driver)| {
+                    signal_drivers.iter().enumerate().any(|(j, other)| {
+                        *i != j && driver.driver != other.driver && driver.overlaps(other)
+                    })
+                })
+                .map(|(_, driver)| driver)
+                .collect();
+
+            if conflicting.is_empty() {
+                continue;
+            }
+
+            let subtype = region
+                .lookup_immediate(&Designator::Identifier(ident.item.clone()))
+                .and_then(|ents| ents.as_non_overloaded())
+                .and_then(|ent| ent.object_subtype());
+
+            if let Some(subtype) = subtype {
+                if !subtype.is_unresolved() {
+                    if self.is_lint_enabled(Lint::ProcessAndConcurrentDriver) {
+                        check_process_and_concurrent_drivers(ident, &conflicting, diagnostics);
+                    }
+                } else {
+                    let mut error = Diagnostic::error(
+                        &ident.pos,
+                        format!(
+                            "Signal '{}' of unresolved subtype '{}' has multiple drivers",
+                            ident.item,
+                            subtype.base().designator()
+                        ),
+                    );
+                    for driver in conflicting {
+                        error.add_related(&driver.pos, "Driven here");
+                    }
+                    diagnostics.push(error);
+                }
+            }
+        }
+    }
+}
+
+/// The part of a signal driven by a process or concurrent assignment
+struct Driver {
+    /// The declaration of the driven signal
+    signal: SrcPos,
+    /// The first assignment of the driven part
+    pos: SrcPos,
+    /// The low and high index of the driven elements, None for the whole signal
+    part: Option<(i64, i64)>,
+    /// Distinguishes the processes and concurrent assignments
+    driver: usize,
+    /// True for the drivers of processes
+    is_process: bool,
+}
+
+impl Driver {
+    fn overlaps(&self, other: &Driver) -> bool {
+        match (self.part, other.part) {
+            (Some((low, high)), Some((other_low, other_high))) => {
+                low <= other_high && other_low <= high
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Warn when a resolved signal is driven both by a process and by a concurrent assignment
+/// which drive overlapping parts of it, which is rarely intended
+fn check_process_and_concurrent_drivers(
+    ident: &Ident,
+    conflicting: &[&&Driver],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let process = conflicting.iter().find(|driver| {
+        driver.is_process
+            && conflicting
+                .iter()
+                .any(|other| !other.is_process && driver.overlaps(other))
+    });
+
+    if let Some(process) = process {
+        let mut warning = Diagnostic::warning(
+            &ident.pos,
+            format!(
+                "Signal '{}' is driven both by a process and by a concurrent assignment",
+                ident.item
+            ),
+        );
+        warning.add_related(&process.pos, "Driven by process here");
+        for other in conflicting.iter() {
+            if !other.is_process && process.overlaps(other) {
+                warning.add_related(&other.pos, "Driven by concurrent assignment here");
+            }
+        }
+        diagnostics.push(warning);
+    }
+}
+
+/// Evaluates a static integer expression such as an index which may refer to constants
+type StaticValue<'a> = dyn Fn(&SrcPos, &Expression) -> Option<i64> + 'a;
+
+/// The low and high bound of a range whose bounds are static
+fn static_range_bounds(
+    range: &RangeConstraint,
+    static_value: &StaticValue<'_>,
+) -> Option<(i64, i64)> {
+    let left = static_value(&range.left_expr.pos, &range.left_expr.item)?;
+    let right = static_value(&range.right_expr.pos, &range.right_expr.item)?;
+    match range.direction {
+        Direction::Ascending => Some((left, right)),
+        Direction::Descending => Some((right, left)),
+    }
+}
+
+/// The signal assigned by a target together with the static range of the assigned elements
+/// The range is None when the whole signal is assigned
+/// The bounds may refer to constants, targets of elements or slices whose bounds are not
+/// static are not counted as drivers since the driven part is unknown, such as an element
+/// indexed by a generate parameter or a slice bounded by a generic
+fn target_signal<'t>(
+    target: &'t WithPos<Target>,
+    static_value: &StaticValue<'_>,
+) -> Option<(&'t SrcPos, Option<(i64, i64)>)> {
+    let name = if let Target::Name(ref name) = target.item {
+        name
+    } else {
+        return None;
+    };
+
+    let (prefix, part) = match name {
+        Name::Designator(..) => (name, None),
+        Name::Indexed(ref prefix, ref indexes) => {
+            let part = if let [index] = indexes.as_slice() {
+                static_value(&index.pos, &index.item).map(|index| (index, index))
+            } else {
+                None
+            };
+            (&prefix.item, Some(part?))
+        }
+        // An indexed name is parsed as a function call
+        Name::FunctionCall(ref fcall) => {
+            let part = if let [AssociationElement {
+                formal: None,
+                actual,
+            }] = fcall.parameters.as_slice()
+            {
+                if let ActualPart::Expression(ref index) = actual.item {
+                    static_value(&actual.pos, index).map(|index| (index, index))
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+            (&fcall.name.item, Some(part?))
+        }
+        Name::Slice(ref prefix, ref drange) => {
+            let part = if let DiscreteRange::Range(crate::ast::Range::Range(ref range)) =
+                drange.as_ref()
+            {
+                static_range_bounds(range, static_value)
+            } else {
+                None
+            };
+            (&prefix.item, Some(part?))
+        }
+        _ => return None,
+    };
+
+    if let Name::Designator(ref designator) = prefix {
+        Some((designator.reference.as_ref()?, part))
+    } else {
+        None
+    }
+}
+
+/// Collect the targets of the signal assignments within a process
+fn collect_sequential_targets<'s>(
+    statements: &'s [LabeledSequentialStatement],
+    targets: &mut Vec<&'s WithPos<Target>>,
+) {
+    for statement in statements.iter() {
+        let target = match statement.statement {
+            SequentialStatement::SignalAssignment(ref assign) => &assign.target,
+            SequentialStatement::SignalForceAssignment(ref assign) => &assign.target,
+            SequentialStatement::If(ref ifstmt) => {
+                for conditional in ifstmt.conditionals.iter() {
+                    collect_sequential_targets(&conditional.item, targets);
+                }
+                if let Some(ref else_item) = ifstmt.else_item {
+                    collect_sequential_targets(else_item, targets);
+                }
+                continue;
+            }
+            SequentialStatement::Case(ref case) => {
+                for alternative in case.alternatives.iter() {
+                    collect_sequential_targets(&alternative.item, targets);
+                }
+                continue;
+            }
+            SequentialStatement::Loop(ref loop_stmt) => {
+                collect_sequential_targets(&loop_stmt.statements, targets);
+                continue;
+            }
+            _ => continue,
+        };
+
+        targets.push(target);
+    }
+}
+
+/// Collect the signals driven by each process and concurrent assignment together with
+/// the position of the first assignment of the driver
+fn collect_drivers(
+    statements: &[LabeledConcurrentStatement],
+    static_value: &StaticValue<'_>,
+    drivers: &mut Vec<Driver>,
+) {
+    for statement in statements.iter() {
+        let driver = drivers.len();
+        match statement.statement {
+            ConcurrentStatement::Assignment(ref assign) => {
+                if let Some((signal, part)) = target_signal(&assign.target, static_value) {
+                    drivers.push(Driver {
+                        signal: signal.clone(),
+                        pos: assign.target.pos.clone(),
+                        part,
+                        driver,
+                        is_process: false,
+                    });
+                }
+            }
+            ConcurrentStatement::Process(ref process) => {
+                let mut targets = Vec::new();
+                collect_sequential_targets(&process.statements, &mut targets);
+
+                // A process has a single driver for each signal it assigns, the parts
+                // assigned by the process are merged into one when they overlap
+                let mut driven: Vec<Driver> = Vec::new();
+                for target in targets {
+                    let (signal, part) = if let Some(signal) = target_signal(target, static_value) {
+                        signal
+                    } else {
+                        continue;
+                    };
+                    let mut merged = Driver {
+                        signal: signal.clone(),
+                        pos: target.pos.clone(),
+                        part,
+                        driver,
+                        is_process: true,
+                    };
+                    while let Some(i) = driven
+                        .iter()
+                        .position(|other| other.signal == merged.signal && other.overlaps(&merged))
+                    {
+                        let other = driven.remove(i);
+                        merged.pos = other.pos;
+                        merged.part = match (other.part, merged.part) {
+                            (Some((low, high)), Some((other_low, other_high))) => {
+                                Some((low.min(other_low), high.max(other_high)))
+                            }
+                            _ => None,
+                        };
+                    }
+                    driven.push(merged);
+                }
+                drivers.append(&mut driven);
+            }
+            ConcurrentStatement::Block(ref block) => {
+                collect_drivers(&block.statements, static_value, drivers);
+            }
+            ConcurrentStatement::IfGenerate(ref gen) => {
+                let mut alternatives: Vec<_> = gen
+                    .conditionals
+                    .iter()
+                    .map(|conditional| &conditional.item.statements)
+                    .collect();
+                if let Some(ref else_item) = gen.else_item {
+                    alternatives.push(&else_item.statements);
+                }
+                collect_alternative_drivers(&alternatives, static_value, drivers);
+            }
+            ConcurrentStatement::CaseGenerate(ref gen) => {
+                let alternatives: Vec<_> = gen
+                    .alternatives
+                    .iter()
+                    .map(|alternative| &alternative.item.statements)
+                    .collect();
+                collect_alternative_drivers(&alternatives, static_value, drivers);
+            }
+            // Counted once regardless of the number of iterations
+            ConcurrentStatement::ForGenerate(ref gen) => {
+                collect_drivers(&gen.body.statements, static_value, drivers);
+            }
+            // Instances and procedure calls would need the mode of the formal
+            ConcurrentStatement::Instance(..)
+            | ConcurrentStatement::ProcedureCall(..)
+            | ConcurrentStatement::Assert(..) => {}
+        }
+    }
+}
+
+/// Only one alternative of a generate statement is elaborated, keep the drivers of the
+/// alternative with the most drivers of each signal
+fn collect_alternative_drivers(
+    alternatives: &[&Vec<LabeledConcurrentStatement>],
+    static_value: &StaticValue<'_>,
+    drivers: &mut Vec<Driver>,
+) {
+    let mut merged: Vec<Driver> = Vec::new();
+
+    for statements in alternatives.iter() {
+        let mut alternative_drivers = Vec::new();
+        collect_drivers(statements, static_value, &mut alternative_drivers);
+
+        let mut signals: Vec<SrcPos> = Vec::new();
+        for driver in alternative_drivers.iter() {
+            if !signals.contains(&driver.signal) {
+                signals.push(driver.signal.clone());
+            }
+        }
+
+        for signal in signals {
+            let count = |drivers: &[Driver]| {
+                drivers
+                    .iter()
+                    .filter(|driver| driver.signal == signal)
+                    .count()
+            };
+
+            if count(&alternative_drivers) > count(&merged) {
+                merged.retain(|driver| driver.signal != signal);
+                let (mut driven, rest) = alternative_drivers
+                    .into_iter()
+                    .partition(|driver| driver.signal == signal);
+                merged.append(&mut driven);
+                alternative_drivers = rest;
+            }
+        }
+    }
+
+    // Keep the drivers distinct from the drivers collected before
+    let offset = drivers.len();
+    for mut driver in merged.into_iter() {
+        driver.driver += offset;
+        drivers.push(driver);
+    }
+}
+
+fn check_passive_procedure_call(
+    pos: &SrcPos,
+    arity: usize,
+    resolved: Option<ResolvedName>,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let entities = if let Some(ResolvedName::Known(entities)) = resolved {
+        entities
+    } else {
+        return;
+    };
+
+    let candidates: Vec<_> = match entities {
+        NamedEntities::Single(ent) => vec![ent],
+        NamedEntities::Overloaded(overloaded) => overloaded.entities().cloned().collect(),
+    }
+    .into_iter()
+    .filter(|ent| {
+        ent.signature().map_or(false, |signature| {
+            signature.return_type().is_none() && signature.can_be_called_with_arity(arity)
+        })
+    })
+    .collect();
+
+    // Only diagnose when every procedure that could be called is active
+    if !candidates.is_empty()
+        && candidates.iter().all(|ent| {
+            ent.signature()
+                .map_or(false, |signature| signature.drives_signals())
+        })
+    {
+        let mut error = Diagnostic::error(
+            pos,
+            format!(
+                "Active procedure call of '{}' is not allowed in an entity statement part",
+                candidates[0].designator()
+            ),
+        );
+        for ent in candidates.iter() {
+            if let Some(decl_pos) = ent.decl_pos() {
+                error.add_related(decl_pos, "Defined here");
+            }
+        }
+        diagnostics.push(error);
+    }
 }
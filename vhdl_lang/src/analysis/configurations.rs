@@ -0,0 +1,68 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::ast::*;
+use crate::data::*;
+
+/// A configuration declaration with the design units it binds
+#[derive(PartialEq, Clone, Debug)]
+pub struct ConfigInfo {
+    pub configuration: UnitId,
+    pub ident: Ident,
+    /// The entity of the configuration when it could be resolved
+    pub entity: Option<UnitId>,
+    /// The block specification of the outermost block configuration
+    pub architecture_name: WithPos<Name>,
+    /// The architecture selected by the outermost block configuration when it exists
+    pub architecture: Option<UnitId>,
+    /// The component configurations of all nested block configurations in the order they appear
+    pub bindings: Vec<BindingInfo>,
+}
+
+/// A component configuration with its binding indication
+#[derive(PartialEq, Clone, Debug)]
+pub struct BindingInfo {
+    pub instantiation_list: InstantiationList,
+    pub component_name: WithPos<SelectedName>,
+    /// None when the component configuration has no binding indication or the binding
+    /// indication has no entity aspect
+    pub entity_aspect: Option<EntityAspect>,
+}
+
+/// The single identifier of a block specification such as 'for rtl'
+pub(super) fn block_spec_identifier(block_config: &BlockConfiguration) -> Option<&Symbol> {
+    if let Name::Designator(ref designator) = block_config.block_spec.item {
+        if let Designator::Identifier(ref sym) = designator.item {
+            return Some(sym);
+        }
+    }
+    None
+}
+
+/// Collect the component configurations of a block configuration and of the block
+/// configurations nested within it
+pub(super) fn collect_bindings(block_config: &BlockConfiguration, bindings: &mut Vec<BindingInfo>) {
+    for item in block_config.items.iter() {
+        match item {
+            ConfigurationItem::Block(ref nested) => {
+                collect_bindings(nested, bindings);
+            }
+            ConfigurationItem::Component(ref component) => {
+                bindings.push(BindingInfo {
+                    instantiation_list: component.spec.instantiation_list.clone(),
+                    component_name: component.spec.component_name.clone(),
+                    entity_aspect: component
+                        .bind_ind
+                        .as_ref()
+                        .and_then(|bind_ind| bind_ind.entity_aspect.clone()),
+                });
+                if let Some(ref nested) = component.block_config {
+                    collect_bindings(nested, bindings);
+                }
+            }
+        }
+    }
+}
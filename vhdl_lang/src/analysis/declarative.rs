@@ -13,7 +13,8 @@ use fnv::FnvHashMap;
 use named_entity::Signature;
 use region::*;
 use std::collections::hash_map::Entry;
-use std::sync::Arc;
+use std::convert::TryFrom;
+use std::sync::{Arc, Weak};
 
 impl<'a> AnalyzeContext<'a> {
     pub fn analyze_declarative_part(
@@ -80,7 +81,12 @@ impl<'a> AnalyzeContext<'a> {
                             }
                         }
                     }
+                    TypeDefinition::ProtectedBody(..) => {
+                        self.analyze_type_declaration(region, type_decl, None, diagnostics)?;
+                    }
                     _ => {
+                        self.lint_casing(DeclarationKind::Type, &type_decl.ident, diagnostics);
+                        self.lint_shadows_standard_name(&type_decl.ident, diagnostics)?;
                         let incomplete_type = incomplete_types.get(type_decl.ident.name());
                         let id = incomplete_type.map(|(id, _)| *id);
                         self.analyze_type_declaration(region, type_decl, id, diagnostics)?;
@@ -200,17 +206,36 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             Declaration::Object(ref mut object_decl) => {
-                self.analyze_subtype_indication(
+                let kind = match object_decl.class {
+                    ObjectClass::Signal => DeclarationKind::Signal,
+                    ObjectClass::Variable | ObjectClass::SharedVariable => {
+                        DeclarationKind::Variable
+                    }
+                    ObjectClass::Constant => DeclarationKind::Constant,
+                };
+                self.lint_casing(kind, &object_decl.ident, diagnostics);
+                self.lint_shadows_standard_name(&object_decl.ident, diagnostics)?;
+
+                let subtype = self.resolve_optional_subtype_indication(
                     region,
                     &mut object_decl.subtype_indication,
                     diagnostics,
                 )?;
+                let mut value = None;
                 if let Some(ref mut expr) = object_decl.expression {
                     self.analyze_expression(region, expr, diagnostics)?;
+                    check_aggregate_choices(&object_decl.subtype_indication, expr, diagnostics);
+                    if let Some(ref subtype) = subtype {
+                        self.analyze_record_aggregate(subtype.base(), expr, diagnostics);
+                        self.check_static_integer_range(region, subtype, expr, diagnostics);
+                    }
+                    if object_decl.class == ObjectClass::Constant {
+                        value = self.static_integer_value(region, expr);
+                    }
                 }
                 region.add(
                     &object_decl.ident,
-                    NamedEntityKind::from_object_declaration(object_decl),
+                    NamedEntityKind::from_object_declaration(object_decl, subtype, value),
                     diagnostics,
                 );
             }
@@ -236,6 +261,7 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_interface_list(&mut region, &mut component.generic_list, diagnostics)?;
                 self.analyze_interface_list(&mut region, &mut component.port_list, diagnostics)?;
                 region.close(diagnostics);
+                self.add_local_region(&region);
             }
             Declaration::Attribute(ref mut attr) => match attr {
                 Attribute::Declaration(ref mut attr_decl) => {
@@ -284,12 +310,18 @@ impl<'a> AnalyzeContext<'a> {
                     &mut body.statements,
                     diagnostics,
                 )?;
+                self.add_local_region(&subpgm_region);
+
+                if self.is_lint_enabled(Lint::UnconditionalRecursion) {
+                    self.lint_unconditional_recursion(body, diagnostics);
+                }
             }
             Declaration::SubprogramDeclaration(ref mut subdecl) => {
                 let mut subpgm_region = region.nested();
                 let signature =
                     self.analyze_subprogram_declaration(&mut subpgm_region, subdecl, diagnostics);
                 subpgm_region.close(diagnostics);
+                self.add_local_region(&subpgm_region);
                 drop(subpgm_region);
 
                 match signature {
@@ -325,6 +357,51 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// Check that a static integer initial value of an object of an integer type can be
+    /// evaluated without overflow and is within the static range of the subtype
+    fn check_static_integer_range(
+        &self,
+        region: &Region<'_>,
+        subtype: &Subtype,
+        expr: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let type_mark = subtype.base();
+        match type_mark.base_type_class() {
+            Some(Type::Integer(..)) => {}
+            _ => return,
+        }
+
+        match self.fold_static_integer_value(region, expr) {
+            Ok(Some(value)) => {
+                if let Some((low, high)) = subtype.range() {
+                    if value < low || value > high {
+                        diagnostics.push(Diagnostic::error(
+                            &expr.pos,
+                            format!(
+                                "Value {} is outside of the range {} to {} of {}",
+                                value,
+                                low,
+                                high,
+                                type_mark.describe()
+                            ),
+                        ));
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(StaticOverflow) => {
+                diagnostics.push(Diagnostic::error(
+                    &expr.pos,
+                    format!(
+                        "Overflow when evaluating static expression of type '{}'",
+                        type_mark.base_type().designator()
+                    ),
+                ));
+            }
+        }
+    }
+
     fn analyze_type_declaration(
         &self,
         parent: &mut Region<'_>,
@@ -339,7 +416,7 @@ impl<'a> AnalyzeContext<'a> {
                 let enum_type = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(Type::Enum, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -358,13 +435,26 @@ impl<'a> AnalyzeContext<'a> {
                     parent.add_named_entity(literal_ent, diagnostics);
                 }
 
+                self.add_implicit_operators(
+                    parent,
+                    &enum_type,
+                    &Type::Enum,
+                    &mut implicit,
+                    diagnostics,
+                );
+
                 // Overwrite enum type with one that contains the implicit declarations
                 // @TODO investigate get_mut_unchecked to change the original enum_type
                 //       this will create a new struct instance and thus the signature of
                 //       the enum literals will not contain the full type declaration of the
                 //       enum type
                 parent.add_named_entity(
-                    Arc::new(enum_type.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit))),
+                    Arc::new(
+                        enum_type.clone_with_kind(NamedEntityKind::TypeDeclaration(
+                            Type::Enum,
+                            implicit,
+                        )),
+                    ),
                     diagnostics,
                 );
             }
@@ -383,6 +473,7 @@ impl<'a> AnalyzeContext<'a> {
                                         &mut body.decl,
                                         diagnostics,
                                     )?;
+                                    self.add_local_region(&region);
                                     parent.add_protected_body(type_decl.ident.clone(), diagnostics);
                                     true
                                 } else {
@@ -416,7 +507,7 @@ impl<'a> AnalyzeContext<'a> {
                 let ptype = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(Type::Protected, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
                 parent.add_named_entity(ptype.clone(), diagnostics);
@@ -432,6 +523,7 @@ impl<'a> AnalyzeContext<'a> {
                                 diagnostics,
                             );
                             subpgm_region.close(diagnostics);
+                            self.add_local_region(&subpgm_region);
                             drop(subpgm_region);
 
                             match signature {
@@ -449,6 +541,7 @@ impl<'a> AnalyzeContext<'a> {
                         }
                     }
                 }
+                self.add_local_region(&region);
                 let region = region.without_parent();
 
                 parent.add_named_entity(
@@ -460,19 +553,42 @@ impl<'a> AnalyzeContext<'a> {
             }
             TypeDefinition::Record(ref mut element_decls) => {
                 let mut region = Region::default();
+                let mut fields = Vec::new();
                 for elem_decl in element_decls.iter_mut() {
                     self.analyze_subtype_indication(parent, &mut elem_decl.subtype, diagnostics)?;
-                    region.add(&elem_decl.ident, NamedEntityKind::RecordField, diagnostics);
+                    let field = Arc::new(NamedEntity::new(
+                        elem_decl.ident.item.clone(),
+                        NamedEntityKind::RecordField,
+                        Some(&elem_decl.ident.pos),
+                    ));
+                    fields.push(field.clone());
+                    region.add_named_entity(field, diagnostics);
                 }
                 region.close(diagnostics);
+                self.add_local_region(&region);
 
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(Type::Record(fields.clone()), Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
-                parent.add_named_entity(type_ent, diagnostics);
+
+                let mut implicit = Vec::new();
+                self.add_implicit_operators(
+                    parent,
+                    &type_ent,
+                    &Type::Record(fields.clone()),
+                    &mut implicit,
+                    diagnostics,
+                );
+                parent.add_named_entity(
+                    Arc::new(type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(
+                        Type::Record(fields),
+                        implicit,
+                    ))),
+                    diagnostics,
+                );
             }
             TypeDefinition::Access(ref mut subtype_indication) => {
                 self.analyze_subtype_indication(parent, subtype_indication, diagnostics)?;
@@ -480,21 +596,43 @@ impl<'a> AnalyzeContext<'a> {
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(Type::Access, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
-                parent.add_named_entity(type_ent, diagnostics);
+
+                let mut implicit = Vec::new();
+                self.add_implicit_operators(
+                    parent,
+                    &type_ent,
+                    &Type::Access,
+                    &mut implicit,
+                    diagnostics,
+                );
+                parent.add_named_entity(
+                    Arc::new(
+                        type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(
+                            Type::Access,
+                            implicit,
+                        )),
+                    ),
+                    diagnostics,
+                );
             }
             TypeDefinition::Array(ref mut array_indexes, ref mut subtype_indication) => {
                 for index in array_indexes.iter_mut() {
                     self.analyze_array_index(parent, index, diagnostics)?;
                 }
-                self.analyze_subtype_indication(parent, subtype_indication, diagnostics)?;
+                let elem_subtype = self.resolve_optional_subtype_indication(
+                    parent,
+                    subtype_indication,
+                    diagnostics,
+                )?;
+                let class = Type::Array(elem_subtype);
 
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(class.clone(), Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -505,8 +643,11 @@ impl<'a> AnalyzeContext<'a> {
                     parent.add_named_entity(to_string.clone(), diagnostics);
                     implicit.push(Arc::downgrade(&to_string));
                 }
+                self.add_implicit_operators(parent, &type_ent, &class, &mut implicit, diagnostics);
                 parent.add_named_entity(
-                    Arc::new(type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit))),
+                    Arc::new(
+                        type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(class, implicit)),
+                    ),
                     diagnostics,
                 );
             }
@@ -527,24 +668,40 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             TypeDefinition::Physical(ref mut physical) => {
+                let type_ent = add_or_overwrite(
+                    parent,
+                    &type_decl.ident,
+                    NamedEntityKind::TypeDeclaration(Type::Physical, Vec::new()),
+                    overwrite_id,
+                    diagnostics,
+                );
+
                 parent.add(
                     physical.primary_unit.clone(),
-                    NamedEntityKind::PhysicalLiteral,
+                    NamedEntityKind::PhysicalLiteral(type_ent.clone()),
                     diagnostics,
                 );
                 for (secondary_unit_name, _) in physical.secondary_units.iter_mut() {
                     parent.add(
                         secondary_unit_name.clone(),
-                        NamedEntityKind::PhysicalLiteral,
+                        NamedEntityKind::PhysicalLiteral(type_ent.clone()),
                         diagnostics,
                     )
                 }
 
-                add_or_overwrite(
+                let mut implicit = Vec::new();
+                self.add_implicit_operators(
                     parent,
-                    &type_decl.ident,
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
-                    overwrite_id,
+                    &type_ent,
+                    &Type::Physical,
+                    &mut implicit,
+                    diagnostics,
+                );
+                parent.add_named_entity(
+                    Arc::new(type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(
+                        Type::Physical,
+                        implicit,
+                    ))),
                     diagnostics,
                 );
             }
@@ -554,10 +711,18 @@ impl<'a> AnalyzeContext<'a> {
 
             TypeDefinition::Integer(ref mut range) => {
                 self.analyze_range(parent, range, diagnostics)?;
+                // Floating point types are parsed as integer types
+                let class = if is_real_range(range) {
+                    Type::Real
+                } else if let ast::Range::Range(ref constraint) = range {
+                    Type::Integer(self.static_range_bounds(parent, constraint))
+                } else {
+                    Type::Integer(None)
+                };
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(class.clone(), Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -568,8 +733,11 @@ impl<'a> AnalyzeContext<'a> {
                     parent.add_named_entity(to_string.clone(), diagnostics);
                     implicit.push(Arc::downgrade(&to_string));
                 }
+                self.add_implicit_operators(parent, &type_ent, &class, &mut implicit, diagnostics);
                 parent.add_named_entity(
-                    Arc::new(type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit))),
+                    Arc::new(
+                        type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(class, implicit)),
+                    ),
                     diagnostics,
                 );
             }
@@ -582,7 +750,7 @@ impl<'a> AnalyzeContext<'a> {
                 let file_type = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::TypeDeclaration(Type::File, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -595,8 +763,8 @@ impl<'a> AnalyzeContext<'a> {
                 // We need to overwrite the type due to circular pointer relations between implicit subprograms
                 // and type declarations
                 let implicit = implicit.iter().map(|ent| Arc::downgrade(ent)).collect();
-                let file_type =
-                    file_type.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit));
+                let file_type = file_type
+                    .clone_with_kind(NamedEntityKind::TypeDeclaration(Type::File, implicit));
                 parent.add_named_entity(Arc::new(file_type), diagnostics);
             }
         }
@@ -640,7 +808,7 @@ impl<'a> AnalyzeContext<'a> {
                 NamedEntityKind::InterfaceObject(InterfaceObject {
                     class: ObjectClass::Constant,
                     mode: Mode::In,
-                    subtype: Subtype::new(string),
+                    subtype: Some(Subtype::new(string)),
                     has_default: false,
                 }),
                 file_type.decl_pos(),
@@ -688,6 +856,126 @@ impl<'a> AnalyzeContext<'a> {
         implicit
     }
 
+    fn create_implicit_operator(
+        &self,
+        type_ent: &NamedEntity,
+        symbol: &str,
+        param_types: &[&Arc<NamedEntity>],
+        return_type: &Arc<NamedEntity>,
+    ) -> Arc<NamedEntity> {
+        let mut params = ParameterList::default();
+        for (name, param_type) in ["L", "R"].iter().zip(param_types.iter()) {
+            params.add_param(Arc::new(NamedEntity::new(
+                self.symbol_utf8(name),
+                NamedEntityKind::InterfaceObject(InterfaceObject {
+                    class: ObjectClass::Constant,
+                    mode: Mode::In,
+                    subtype: Some(Subtype::new((*param_type).clone())),
+                    has_default: false,
+                }),
+                type_ent.decl_pos(),
+            )));
+        }
+
+        Arc::new(NamedEntity::implicit(
+            Designator::OperatorSymbol(Latin1String::new(symbol.as_bytes())),
+            NamedEntityKind::Subprogram(Signature::new(params, Some(return_type.clone()))),
+            type_ent.decl_pos(),
+        ))
+    }
+
+    /// The BOOLEAN type returned by the implicit predefined operators
+    /// Within the standard package it is looked up among the declarations made so far
+    fn boolean_type(
+        &self,
+        parent: &Region<'_>,
+        type_ent: &Arc<NamedEntity>,
+    ) -> Option<Arc<NamedEntity>> {
+        if !self.is_standard_package() {
+            return self.standard_type("BOOLEAN");
+        }
+
+        let designator = self.symbol_utf8("BOOLEAN").into();
+        if *type_ent.designator() == designator {
+            Some(type_ent.clone())
+        } else {
+            parent
+                .lookup_immediate(&designator)?
+                .as_non_overloaded()
+                .cloned()
+        }
+    }
+
+    /// Add the implicit predefined operators of a type
+    /// Equality is defined for all types except file and protected types, ordering for
+    /// scalar types and concatenation for array types
+    fn add_implicit_operators(
+        &self,
+        parent: &mut Region<'_>,
+        type_ent: &Arc<NamedEntity>,
+        class: &Type,
+        implicit: &mut Vec<Weak<NamedEntity>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let boolean = if let Some(boolean) = self.boolean_type(parent, type_ent) {
+            boolean
+        } else {
+            return;
+        };
+
+        let mut operators = Vec::new();
+        for symbol in ["=", "/="].iter() {
+            operators.push(self.create_implicit_operator(
+                type_ent,
+                symbol,
+                &[type_ent, type_ent],
+                &boolean,
+            ));
+        }
+
+        match class {
+            Type::Enum | Type::Integer(..) | Type::Real | Type::Physical => {
+                for symbol in ["<", "<=", ">", ">="].iter() {
+                    operators.push(self.create_implicit_operator(
+                        type_ent,
+                        symbol,
+                        &[type_ent, type_ent],
+                        &boolean,
+                    ));
+                }
+            }
+            Type::Array(elem_subtype) => {
+                operators.push(self.create_implicit_operator(
+                    type_ent,
+                    "&",
+                    &[type_ent, type_ent],
+                    type_ent,
+                ));
+                if let Some(elem_subtype) = elem_subtype {
+                    let elem_type = elem_subtype.base();
+                    operators.push(self.create_implicit_operator(
+                        type_ent,
+                        "&",
+                        &[type_ent, elem_type],
+                        type_ent,
+                    ));
+                    operators.push(self.create_implicit_operator(
+                        type_ent,
+                        "&",
+                        &[elem_type, type_ent],
+                        type_ent,
+                    ));
+                }
+            }
+            Type::Record(..) | Type::Access | Type::File | Type::Protected => {}
+        }
+
+        for ent in operators.into_iter() {
+            implicit.push(Arc::downgrade(&ent));
+            parent.add_named_entity(ent, diagnostics);
+        }
+    }
+
     /// Create implicit TO_STRING
     /// function TO_STRING (VALUE: T) return STRING;
     pub fn create_to_string(&self, type_ent: Arc<NamedEntity>) -> NamedEntity {
@@ -707,7 +995,7 @@ impl<'a> AnalyzeContext<'a> {
             NamedEntityKind::InterfaceObject(InterfaceObject {
                 class: ObjectClass::Constant,
                 mode: Mode::In,
-                subtype: Subtype::new(type_ent.clone()),
+                subtype: Some(Subtype::new(type_ent.clone())),
                 has_default: false,
             }),
             type_ent.decl_pos(),
@@ -795,7 +1083,7 @@ impl<'a> AnalyzeContext<'a> {
                     NamedEntityKind::InterfaceObject(InterfaceObject {
                         class: object_decl.class,
                         mode: object_decl.mode,
-                        subtype,
+                        subtype: Some(subtype),
                         has_default: object_decl.expression.is_some(),
                     }),
                     Some(&object_decl.ident.pos),
@@ -811,6 +1099,7 @@ impl<'a> AnalyzeContext<'a> {
                 let signature =
                     self.analyze_subprogram_declaration(&mut subpgm_region, subpgm, diagnostics);
                 subpgm_region.close(diagnostics);
+                self.add_local_region(&subpgm_region);
                 drop(subpgm_region);
 
                 let kind = NamedEntityKind::Subprogram(signature?);
@@ -846,6 +1135,21 @@ impl<'a> AnalyzeContext<'a> {
                 }
                 Err(err) => {
                     err.add_to(diagnostics)?;
+
+                    // Declare an interface object of unknown subtype to avoid further errors at each use
+                    // while keeping its mode and its position in the interface list
+                    if let InterfaceDeclaration::Object(ref object_decl) = decl {
+                        region.add(
+                            &object_decl.ident,
+                            NamedEntityKind::InterfaceObject(InterfaceObject {
+                                class: object_decl.class,
+                                mode: object_decl.mode,
+                                subtype: None,
+                                has_default: object_decl.expression.is_some(),
+                            }),
+                            diagnostics,
+                        );
+                    }
                 }
             }
         }
@@ -939,18 +1243,45 @@ impl<'a> AnalyzeContext<'a> {
     ) -> AnalysisResult<Subtype> {
         // @TODO more
         let SubtypeIndication {
+            resolution,
             type_mark,
             constraint,
-            ..
         } = subtype_indication;
 
         let base_type = self.resolve_type_mark(region, type_mark)?;
 
-        if let Some(constraint) = constraint {
+        let (length, direction, range) = if let Some(constraint) = constraint {
             self.analyze_subtype_constraint(region, &mut constraint.item, diagnostics)?;
-        }
+            (
+                self.static_array_length(region, &constraint.item),
+                self.static_array_direction(region, &constraint.item),
+                self.static_scalar_range(region, &constraint.item),
+            )
+        } else {
+            (None, None, None)
+        };
 
-        Ok(Subtype::new(base_type))
+        let resolved = *resolution != ResolutionIndication::Unresolved;
+        Ok(Subtype::with_length(base_type, length)
+            .with_resolution(resolved)
+            .with_direction(direction)
+            .with_range(range))
+    }
+
+    /// Resolve a subtype indication and add any error to diagnostics
+    pub fn resolve_optional_subtype_indication(
+        &self,
+        region: &Region<'_>,
+        subtype_indication: &mut SubtypeIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<Subtype>> {
+        match self.resolve_subtype_indication(region, subtype_indication, diagnostics) {
+            Ok(subtype) => Ok(Some(subtype)),
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                Ok(None)
+            }
+        }
     }
 
     pub fn analyze_subtype_indication(
@@ -985,6 +1316,303 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
     }
+
+    /// Evaluate an analyzed integer expression consisting of literals, constants with a
+    /// static value and the 'low and 'high attributes of types with a static range
+    pub fn static_integer_value(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+    ) -> Option<i64> {
+        self.fold_static_integer_value(region, expr).ok().flatten()
+    }
+
+    /// Evaluate an analyzed integer expression like static_integer_value where the
+    /// expression has no position of its own, such as the actual of an association
+    pub fn static_integer_value_at(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        expr: &Expression,
+    ) -> Option<i64> {
+        let name_value = |name: &Name| self.static_name_value(region, pos, name);
+        fold_static_integer(expr, &name_value).ok().flatten()
+    }
+
+    fn fold_static_integer_value(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+    ) -> Result<Option<i64>, StaticOverflow> {
+        let name_value = |name: &Name| self.static_name_value(region, &expr.pos, name);
+        fold_static_integer(&expr.item, &name_value)
+    }
+
+    fn static_name_value(&self, region: &Region<'_>, pos: &SrcPos, name: &Name) -> Option<i64> {
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                let ent = self
+                    .lookup_name(region, pos, name)?
+                    .into_non_overloaded()
+                    .ok()?;
+                if let NamedEntityKind::Object(ObjectClass::Constant, _, value) = ent.actual_kind()
+                {
+                    *value
+                } else {
+                    None
+                }
+            }
+            Name::Attribute(ref attr) if attr.signature.is_none() && attr.expr.is_none() => {
+                let ent = self
+                    .lookup_name(region, &attr.name.pos, &attr.name.item)?
+                    .into_non_overloaded()
+                    .ok()?;
+                let (low, high) = ent.static_range()?;
+                match attr.attr.item.name_utf8().to_lowercase().as_str() {
+                    "low" => Some(low),
+                    "high" => Some(high),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The low and high bound of an analyzed range with static bounds which may refer to
+    /// constants
+    fn static_range_bounds(
+        &self,
+        region: &Region<'_>,
+        range: &RangeConstraint,
+    ) -> Option<(i64, i64)> {
+        let left = self.static_integer_value(region, &range.left_expr)?;
+        let right = self.static_integer_value(region, &range.right_expr)?;
+        match range.direction {
+            Direction::Ascending => Some((left, right)),
+            Direction::Descending => Some((right, left)),
+        }
+    }
+
+    /// The number of elements of a one dimensional array constraint with static bounds
+    fn static_array_length(
+        &self,
+        region: &Region<'_>,
+        constraint: &SubtypeConstraint,
+    ) -> Option<u64> {
+        let (low, high) = self.static_range_bounds(region, static_array_range(constraint)?)?;
+        if high < low {
+            return Some(0);
+        }
+        // The length of a range covering most of i64 is not representable
+        let length = high.checked_sub(low).and_then(|diff| diff.checked_add(1))?;
+        Some(length as u64)
+    }
+
+    /// The low and high bound of a scalar range constraint with static bounds
+    fn static_scalar_range(
+        &self,
+        region: &Region<'_>,
+        constraint: &SubtypeConstraint,
+    ) -> Option<(i64, i64)> {
+        if let SubtypeConstraint::Range(ast::Range::Range(ref range)) = constraint {
+            self.static_range_bounds(region, range)
+        } else {
+            None
+        }
+    }
+
+    /// The direction of a one dimensional array constraint with static bounds
+    fn static_array_direction(
+        &self,
+        region: &Region<'_>,
+        constraint: &SubtypeConstraint,
+    ) -> Option<Direction> {
+        let range = static_array_range(constraint)?;
+        self.static_range_bounds(region, range)?;
+        Some(range.direction)
+    }
+}
+
+/// Evaluate an integer expression consisting only of literals
+pub(super) fn static_integer(expr: &Expression) -> Option<i64> {
+    fold_static_integer(expr, &|_| None).ok().flatten()
+}
+
+/// A static integer expression with a value or an intermediate value which does not fit in
+/// the 64 bits used for evaluation
+struct StaticOverflow;
+
+/// Evaluate an integer expression consisting of literals and names with a static value
+/// Returns None when the expression is not static
+fn fold_static_integer(
+    expr: &Expression,
+    name_value: &dyn Fn(&Name) -> Option<i64>,
+) -> Result<Option<i64>, StaticOverflow> {
+    match expr {
+        Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) => {
+            i64::try_from(*value).map(Some).map_err(|_| StaticOverflow)
+        }
+        Expression::Unary(Unary::Minus, ref inner) => {
+            match fold_static_integer(&inner.item, name_value)? {
+                Some(value) => value.checked_neg().map(Some).ok_or(StaticOverflow),
+                None => Ok(None),
+            }
+        }
+        Expression::Unary(Unary::Plus, ref inner) => fold_static_integer(&inner.item, name_value),
+        Expression::Binary(op, ref left, ref right) => {
+            let left = fold_static_integer(&left.item, name_value)?;
+            let right = fold_static_integer(&right.item, name_value)?;
+            let (left, right) = if let (Some(left), Some(right)) = (left, right) {
+                (left, right)
+            } else {
+                return Ok(None);
+            };
+
+            let value = match op {
+                Binary::Plus => left.checked_add(right),
+                Binary::Minus => left.checked_sub(right),
+                Binary::Times => left.checked_mul(right),
+                Binary::Div => {
+                    if right == 0 {
+                        return Ok(None);
+                    }
+                    left.checked_div(right)
+                }
+                Binary::Pow => {
+                    if let Ok(exponent) = u32::try_from(right) {
+                        left.checked_pow(exponent)
+                    } else if right < 0 || left.abs() <= 1 {
+                        // Negative exponents are not allowed for integers
+                        return Ok(None);
+                    } else {
+                        None
+                    }
+                }
+                _ => return Ok(None),
+            };
+            value.map(Some).ok_or(StaticOverflow)
+        }
+        Expression::Name(ref name) => Ok(name_value(name)),
+        _ => Ok(None),
+    }
+}
+
+/// The low and high bound of a range with static bounds
+pub(super) fn static_bounds(range: &RangeConstraint) -> Option<(i64, i64)> {
+    let left = static_integer(&range.left_expr.item)?;
+    let right = static_integer(&range.right_expr.item)?;
+    match range.direction {
+        Direction::Ascending => Some((left, right)),
+        Direction::Descending => Some((right, left)),
+    }
+}
+
+/// The range of a one dimensional array constraint with static bounds
+fn static_array_range(constraint: &SubtypeConstraint) -> Option<&RangeConstraint> {
+    if let SubtypeConstraint::Array(ref ranges, _) = constraint {
+        if let [DiscreteRange::Range(ast::Range::Range(ref range))] = ranges.as_slice() {
+            return Some(range);
+        }
+    }
+    None
+}
+
+fn describe_range(range: &RangeConstraint) -> Option<String> {
+    let left = static_integer(&range.left_expr.item)?;
+    let right = static_integer(&range.right_expr.item)?;
+    let direction = match range.direction {
+        Direction::Ascending => "to",
+        Direction::Descending => "downto",
+    };
+    Some(format!("{} {} {}", left, direction, right))
+}
+
+/// Check that static choices of an array aggregate are within the static index range of the
+/// subtype and that they do not overlap
+fn check_aggregate_choices(
+    subtype_indication: &SubtypeIndication,
+    expr: &WithPos<Expression>,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let index_range = if let Some(range) = subtype_indication
+        .constraint
+        .as_ref()
+        .and_then(|constraint| static_array_range(&constraint.item))
+    {
+        range
+    } else {
+        return;
+    };
+
+    let (low, high) = if let Some(bounds) = static_bounds(index_range) {
+        bounds
+    } else {
+        return;
+    };
+
+    let assocs = if let Expression::Aggregate(ref assocs) = expr.item {
+        assocs
+    } else {
+        return;
+    };
+
+    let mut chosen: Vec<(i64, i64, SrcPos)> = Vec::new();
+    for assoc in assocs.iter() {
+        let choices = if let ElementAssociation::Named(ref choices, _) = assoc {
+            choices
+        } else {
+            continue;
+        };
+
+        for choice in choices.iter() {
+            let (choice_low, choice_high, pos, description) = match choice {
+                Choice::Expression(ref expr) => {
+                    if let Some(value) = static_integer(&expr.item) {
+                        (value, value, expr.pos.clone(), value.to_string())
+                    } else {
+                        continue;
+                    }
+                }
+                Choice::DiscreteRange(DiscreteRange::Range(ast::Range::Range(ref range))) => {
+                    if let (Some((choice_low, choice_high)), Some(description)) =
+                        (static_bounds(range), describe_range(range))
+                    {
+                        if choice_low > choice_high {
+                            // Null range
+                            continue;
+                        }
+                        let pos = range.left_expr.pos.combine(&range.right_expr.pos);
+                        (choice_low, choice_high, pos, description)
+                    } else {
+                        continue;
+                    }
+                }
+                _ => continue,
+            };
+
+            if choice_low < low || choice_high > high {
+                diagnostics.push(Diagnostic::error(
+                    &pos,
+                    format!(
+                        "Choice {} is outside of index range {}",
+                        description,
+                        describe_range(index_range).unwrap_or_default()
+                    ),
+                ));
+            } else if let Some((_, _, prev_pos)) = chosen.iter().find(|(prev_low, prev_high, _)| {
+                choice_low <= *prev_high && *prev_low <= choice_high
+            }) {
+                let mut error = Diagnostic::error(
+                    &pos,
+                    format!("Choice {} overlaps with a previous choice", description),
+                );
+                error.add_related(prev_pos, "Previously chosen here");
+                diagnostics.push(error);
+            }
+
+            chosen.push((choice_low, choice_high, pos));
+        }
+    }
 }
 
 fn find_full_type_definition<'a>(
@@ -1008,6 +1636,25 @@ fn find_full_type_definition<'a>(
     None
 }
 
+/// True if the range of an integer type definition has real bounds
+/// which means it is actually a floating point type definition
+fn is_real_range(range: &ast::Range) -> bool {
+    fn is_real(expr: &Expression) -> bool {
+        match expr {
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Real(..))) => true,
+            Expression::Unary(Unary::Minus, ref expr)
+            | Expression::Unary(Unary::Plus, ref expr) => is_real(&expr.item),
+            _ => false,
+        }
+    }
+
+    if let ast::Range::Range(ref constraint) = range {
+        is_real(&constraint.left_expr.item) || is_real(&constraint.right_expr.item)
+    } else {
+        false
+    }
+}
+
 fn add_or_overwrite(
     region: &mut Region,
     name: &Ident,
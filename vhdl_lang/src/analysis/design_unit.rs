@@ -10,7 +10,7 @@ use crate::data::*;
 use analyze::*;
 use region::*;
 use root::*;
-use semantic::{uninstantiated_package_prefix_error, ResolvedName};
+use semantic::{missing_library_clause_error, uninstantiated_package_prefix_error, ResolvedName};
 use std::sync::Arc;
 
 impl<'a> AnalyzeContext<'a> {
@@ -42,9 +42,11 @@ impl<'a> AnalyzeContext<'a> {
             },
             AnyDesignUnit::Secondary(unit) => match unit {
                 AnySecondaryUnit::Architecture(unit) => {
-                    self.analyze_architecture(id, unit, diagnostics)
+                    self.analyze_architecture(id, unit, region, diagnostics)
+                }
+                AnySecondaryUnit::PackageBody(unit) => {
+                    self.analyze_package_body(unit, region, diagnostics)
                 }
-                AnySecondaryUnit::PackageBody(unit) => self.analyze_package_body(unit, diagnostics),
             },
         }
     }
@@ -81,7 +83,7 @@ impl<'a> AnalyzeContext<'a> {
             self.analyze_interface_list(&mut primary_region, list, diagnostics)?;
         }
         self.analyze_declarative_part(&mut primary_region, &mut unit.decl, diagnostics)?;
-        self.analyze_concurrent_part(&mut primary_region, &mut unit.statements, diagnostics)?;
+        self.analyze_entity_statement_part(&mut primary_region, &mut unit.statements, diagnostics)?;
 
         *region = primary_region.without_parent();
 
@@ -204,6 +206,7 @@ impl<'a> AnalyzeContext<'a> {
         &self,
         id: EntityId,
         unit: &mut ArchitectureBody,
+        region: &mut Region<'_>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         unit.entity_name.clear_reference();
@@ -225,10 +228,10 @@ impl<'a> AnalyzeContext<'a> {
 
         let mut root_region = Region::default().with_parent(&entity.result().root_region);
         self.analyze_context_clause(&mut root_region, &mut unit.context_clause, diagnostics)?;
-        let mut region = Region::extend(&entity.result().region, Some(&root_region));
+        let mut secondary_region = Region::extend(&entity.result().region, Some(&root_region));
 
         // Architecture name is visible
-        region.make_potentially_visible(
+        secondary_region.make_potentially_visible(
             Some(unit.pos()),
             Arc::new(NamedEntity::new_with_id(
                 id,
@@ -238,15 +241,25 @@ impl<'a> AnalyzeContext<'a> {
             )),
         );
 
-        self.analyze_declarative_part(&mut region, &mut unit.decl, diagnostics)?;
-        self.analyze_concurrent_part(&mut region, &mut unit.statements, diagnostics)?;
-        region.close(diagnostics);
+        self.analyze_declarative_part(&mut secondary_region, &mut unit.decl, diagnostics)?;
+        self.analyze_concurrent_part(&mut secondary_region, &mut unit.statements, diagnostics)?;
+        self.check_multiple_drivers(&secondary_region, &unit.decl, &unit.statements, diagnostics);
+        secondary_region.close(diagnostics);
+        *region = secondary_region.without_parent();
+
+        if self.is_lint_enabled(Lint::SignalCouldBeConstant) {
+            self.lint_signal_could_be_constant(&unit.decl, &unit.statements, diagnostics);
+        }
+        if self.is_lint_enabled(Lint::DefaultInitialValue) {
+            self.lint_default_initial_value(&unit.decl, &unit.statements, diagnostics);
+        }
         Ok(())
     }
 
     fn analyze_package_body(
         &self,
         unit: &mut PackageBody,
+        region: &mut Region<'_>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         unit.ident.clear_reference();
@@ -269,10 +282,11 @@ impl<'a> AnalyzeContext<'a> {
         let mut root_region = Region::default().with_parent(&package.result().root_region);
         self.analyze_context_clause(&mut root_region, &mut unit.context_clause, diagnostics)?;
 
-        let mut region = Region::extend(&package.result().region, Some(&root_region));
+        let mut secondary_region = Region::extend(&package.result().region, Some(&root_region));
 
-        self.analyze_declarative_part(&mut region, &mut unit.decl, diagnostics)?;
-        region.close(diagnostics);
+        self.analyze_declarative_part(&mut secondary_region, &mut unit.decl, diagnostics)?;
+        secondary_region.close(diagnostics);
+        *region = secondary_region.without_parent();
         Ok(())
     }
 
@@ -416,6 +430,21 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    /// True when the name is within a library of a library clause which does not exist
+    fn has_missing_library_prefix(&self, region: &Region<'_>, name: &WithPos<Name>) -> bool {
+        match name.item {
+            Name::Selected(ref prefix, _) | Name::SelectedAll(ref prefix) => {
+                self.has_missing_library_prefix(region, prefix)
+            }
+            Name::Designator(ref designator) => region
+                .lookup_within(&name.pos, designator.designator())
+                .ok()
+                .and_then(|visible| visible.into_non_overloaded().ok())
+                .map_or(false, |ent| self.is_missing_library(&ent)),
+            _ => false,
+        }
+    }
+
     fn resolve_context_item_name(
         &self,
         region: &Region<'_>,
@@ -444,7 +473,20 @@ impl<'a> AnalyzeContext<'a> {
             }
             Name::Designator(designator) => {
                 designator.clear_reference();
-                let visible = region.lookup_within(&name.pos, designator.designator())?;
+                let visible = match region.lookup_within(&name.pos, designator.designator()) {
+                    Ok(visible) => visible,
+                    Err(err) => {
+                        if let Designator::Identifier(ref library_name) = designator.designator() {
+                            if self.get_library(library_name).is_some() {
+                                return Err(AnalysisError::NotFatal(missing_library_clause_error(
+                                    &name.pos,
+                                    library_name,
+                                )));
+                            }
+                        }
+                        return Err(err.into());
+                    }
+                };
                 designator.set_reference(&visible);
                 Ok(UsedNames::Single(visible))
             }
@@ -481,6 +523,14 @@ impl<'a> AnalyzeContext<'a> {
                                 &library_name,
                                 format!("No such library '{}'", library_name.item),
                             ));
+
+                            // Declare the missing library to report it only at the library clause
+                            let library = Arc::new(NamedEntity::new(
+                                Designator::Identifier(library_name.item.clone()),
+                                NamedEntityKind::Library,
+                                Some(&library_name.pos),
+                            ));
+                            region.make_potentially_visible(Some(&library_name.pos), library);
                         }
                     }
                 }
@@ -558,6 +608,10 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
 
+            if self.has_missing_library_prefix(region, name) {
+                continue;
+            }
+
             match self.resolve_context_item_name(&region, name) {
                 Ok(UsedNames::Single(visible)) => {
                     visible.make_potentially_visible_in(Some(&name.pos), region);
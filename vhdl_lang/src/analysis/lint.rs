@@ -0,0 +1,1155 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use super::analyze::*;
+use super::processes::ClockEdge;
+use super::region::*;
+use crate::ast::search::{FindAllReferences, Search};
+use crate::ast::*;
+use crate::data::*;
+
+/// Heuristic checks which are not enabled by default
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum Lint {
+    /// A variable of a clocked process which is copied to another variable after being
+    /// assigned within the same clock edge, suggesting that signal semantics were expected
+    VariableAsSignal,
+    /// A subprogram which calls itself without any condition, thus never terminating
+    UnconditionalRecursion,
+    /// Identifiers of a kind of declaration which do not follow the casing convention
+    Casing(DeclarationKind, CasingConvention),
+    /// A user declaration with the same name as a declaration of a standard package
+    ShadowsStandardName,
+    /// An architecture signal which is only assigned a literal value once
+    SignalCouldBeConstant,
+    /// An array assigned or connected to an array whose static range has the opposite direction
+    DirectionMismatch,
+    /// An architecture signal without initial value which is read but never assigned or which a
+    /// process reads before assigning it, relying on the default initial value of its type
+    DefaultInitialValue,
+    /// A resolved signal driven both by a process and by a concurrent assignment
+    ProcessAndConcurrentDriver,
+}
+
+/// Packages whose declarations are considered standard names
+const STANDARD_PACKAGES: &[(&str, &str)] = &[
+    ("std", "standard"),
+    ("ieee", "std_logic_1164"),
+    ("ieee", "numeric_std"),
+];
+
+/// Libraries provided together with the language server, which follow their own conventions
+const BUNDLED_LIBRARIES: &[&str] = &["std", "ieee"];
+
+/// Kinds of declarations which a casing convention is applied to
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum DeclarationKind {
+    Signal,
+    Variable,
+    Constant,
+    Type,
+    /// Any signal, variable or constant
+    Object,
+}
+
+/// Casing conventions of identifiers
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Casing {
+    /// lower_snake_case
+    LowerSnakeCase,
+    /// UPPER_SNAKE_CASE
+    UpperSnakeCase,
+    /// PascalCase
+    PascalCase,
+}
+
+/// A casing convention with an optional literal prefix and suffix, such as C_{UPPER_SNAKE_CASE}
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CasingConvention {
+    prefix: String,
+    casing: Casing,
+    suffix: String,
+}
+
+impl DeclarationKind {
+    pub fn describe(&self) -> &str {
+        match self {
+            DeclarationKind::Signal => "signal",
+            DeclarationKind::Variable => "variable",
+            DeclarationKind::Constant => "constant",
+            DeclarationKind::Type => "type",
+            DeclarationKind::Object => "object",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<DeclarationKind> {
+        match name {
+            "signal" => Some(DeclarationKind::Signal),
+            "variable" => Some(DeclarationKind::Variable),
+            "constant" => Some(DeclarationKind::Constant),
+            "type" => Some(DeclarationKind::Type),
+            "object" => Some(DeclarationKind::Object),
+            _ => None,
+        }
+    }
+
+    /// True if a declaration of the other kind is also of this kind
+    fn includes(&self, other: DeclarationKind) -> bool {
+        match self {
+            DeclarationKind::Object => other != DeclarationKind::Type,
+            _ => *self == other,
+        }
+    }
+}
+
+impl Casing {
+    pub fn describe(&self) -> &str {
+        match self {
+            Casing::LowerSnakeCase => "lower_snake_case",
+            Casing::UpperSnakeCase => "UPPER_SNAKE_CASE",
+            Casing::PascalCase => "PascalCase",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Casing> {
+        [
+            Casing::LowerSnakeCase,
+            Casing::UpperSnakeCase,
+            Casing::PascalCase,
+        ]
+        .iter()
+        .find(|casing| casing.describe() == name)
+        .cloned()
+    }
+
+    /// Convert the identifier to this casing convention
+    pub fn apply(&self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            Casing::LowerSnakeCase => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Casing::UpperSnakeCase => words
+                .iter()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Casing::PascalCase => words
+                .iter()
+                .map(|word| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) => {
+                            first.to_uppercase().collect::<String>()
+                                + &chars.as_str().to_lowercase()
+                        }
+                        None => String::new(),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CasingConvention {
+    /// Parse a pattern such as C_{UPPER_SNAKE_CASE} or {lower_snake_case}_t where the
+    /// casing within braces applies to the identifier without the prefix and suffix
+    pub fn from_pattern(pattern: &str) -> Result<CasingConvention, String> {
+        let invalid = || {
+            format!(
+                "Invalid casing pattern '{}', expected one of {{lower_snake_case}}, {{UPPER_SNAKE_CASE}} or {{PascalCase}} with an optional prefix and suffix",
+                pattern
+            )
+        };
+
+        let start = pattern.find('{').ok_or_else(invalid)?;
+        let end = pattern.rfind('}').ok_or_else(invalid)?;
+        if end < start {
+            return Err(invalid());
+        }
+
+        let casing = Casing::from_name(&pattern[start + 1..end]).ok_or_else(invalid)?;
+        Ok(CasingConvention {
+            prefix: pattern[..start].to_owned(),
+            casing,
+            suffix: pattern[end + 1..].to_owned(),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            self.casing.describe().to_owned()
+        } else {
+            format!(
+                "{}{{{}}}{}",
+                self.prefix,
+                self.casing.describe(),
+                self.suffix
+            )
+        }
+    }
+
+    /// Convert the identifier to this casing convention, an existing prefix or suffix
+    /// in any casing is replaced
+    pub fn apply(&self, name: &str) -> String {
+        let mut stem = name;
+        if let Some(prefix) = stem.get(..self.prefix.len()) {
+            if stem.len() > prefix.len() && prefix.eq_ignore_ascii_case(&self.prefix) {
+                stem = &stem[prefix.len()..];
+            }
+        }
+        let split = stem.len().saturating_sub(self.suffix.len());
+        if let Some(suffix) = stem.get(split..) {
+            if split > 0 && suffix.eq_ignore_ascii_case(&self.suffix) {
+                stem = &stem[..split];
+            }
+        }
+
+        format!("{}{}{}", self.prefix, self.casing.apply(stem), self.suffix)
+    }
+}
+
+impl From<Casing> for CasingConvention {
+    fn from(casing: Casing) -> CasingConvention {
+        CasingConvention {
+            prefix: String::new(),
+            casing,
+            suffix: String::new(),
+        }
+    }
+}
+
+/// Split an identifier into words at underscores and where a lower case letter or digit
+/// is followed by an upper case letter
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_is_lower = false;
+
+    for chr in name.chars() {
+        if chr == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            prev_is_lower = false;
+            continue;
+        }
+
+        if chr.is_uppercase() && prev_is_lower && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        prev_is_lower = chr.is_lowercase() || chr.is_ascii_digit();
+        word.push(chr);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn referenced_designator(name: &Name) -> Option<&WithRef<Designator>> {
+    match name {
+        Name::Designator(designator) => Some(designator),
+        Name::Selected(_, designator) => Some(&designator.item),
+        _ => None,
+    }
+}
+
+fn target_reference(target: &Target) -> Option<&SrcPos> {
+    if let Target::Name(ref name) = target {
+        referenced_designator(name).and_then(|designator| designator.reference.as_ref())
+    } else {
+        None
+    }
+}
+
+/// The subprogram of a body which is checked for calls of itself
+struct CalledSubprogram<'a> {
+    designator: &'a Designator,
+    decl_pos: &'a SrcPos,
+    num_params: usize,
+}
+
+impl<'a> CalledSubprogram<'a> {
+    /// True if the call resolves to the subprogram
+    fn is_fcall(&self, fcall: &FunctionCall) -> bool {
+        referenced_designator(&fcall.name.item)
+            .and_then(|called| called.reference.as_ref())
+            .map(|pos| pos == self.decl_pos)
+            .unwrap_or(false)
+    }
+
+    fn is_call(&self, name: &Name) -> bool {
+        match name {
+            Name::FunctionCall(ref fcall) => self.is_fcall(fcall),
+            // A call without arguments is not resolved among the overloaded subprograms
+            Name::Designator(ref called) => match called.reference {
+                Some(ref pos) => pos == self.decl_pos,
+                None => self.num_params == 0 && &called.item == self.designator,
+            },
+            _ => false,
+        }
+    }
+
+    /// Find a call of the subprogram which is always evaluated as part of the expression
+    fn find_in_expression<'e>(&self, expr: &'e WithPos<Expression>) -> Option<&'e SrcPos> {
+        match expr.item {
+            // The right operand of a short-circuit operator is not always evaluated
+            Expression::Binary(Binary::And, ref left, _)
+            | Expression::Binary(Binary::Or, ref left, _)
+            | Expression::Binary(Binary::Nand, ref left, _)
+            | Expression::Binary(Binary::Nor, ref left, _) => self.find_in_expression(left),
+            Expression::Binary(_, ref left, ref right) => self
+                .find_in_expression(left)
+                .or_else(|| self.find_in_expression(right)),
+            Expression::Unary(_, ref inner) => self.find_in_expression(inner),
+            Expression::Name(ref name) => {
+                if self.is_call(name) {
+                    Some(&expr.pos)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The signal referenced by the prefix of an assignment target or actual
+fn referenced_signal(name: &Name) -> Option<&SrcPos> {
+    match name {
+        Name::Designator(ref designator) => designator.reference.as_ref(),
+        Name::Selected(ref prefix, ref designator) => designator
+            .item
+            .reference
+            .as_ref()
+            .or_else(|| referenced_signal(&prefix.item)),
+        Name::Indexed(ref prefix, ..) | Name::Slice(ref prefix, ..) => {
+            referenced_signal(&prefix.item)
+        }
+        // An indexed name is parsed as a function call
+        Name::FunctionCall(ref fcall) => referenced_signal(&fcall.name.item),
+        _ => None,
+    }
+}
+
+/// True for literals and aggregates of literals such as (others => '0')
+fn is_literal(expr: &Expression) -> bool {
+    match expr {
+        Expression::Literal(..) => true,
+        Expression::Aggregate(ref assocs) => assocs.iter().all(|assoc| match assoc {
+            ElementAssociation::Positional(ref expr) | ElementAssociation::Named(_, ref expr) => {
+                is_literal(&expr.item)
+            }
+        }),
+        _ => false,
+    }
+}
+
+/// A waveform of a single literal without delay
+fn is_literal_waveform(rhs: &AssignmentRightHand<Waveform>) -> bool {
+    if let AssignmentRightHand::Simple(Waveform::Elements(ref elems)) = rhs {
+        if let [WaveformElement { value, after: None }] = elems.as_slice() {
+            return is_literal(&value.item);
+        }
+    }
+    false
+}
+
+fn describe_direction(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Ascending => "to",
+        Direction::Descending => "downto",
+    }
+}
+
+/// How a signal declared in an architecture is driven
+struct SignalDrivers {
+    ident: Ident,
+    /// The number of assignments with the signal as target
+    assignments: usize,
+    /// True if all assignments are unconditional concurrent assignments of a literal
+    literal: bool,
+    /// True if the signal is the actual of a port or procedure call association
+    connected: bool,
+}
+
+struct ConstantSignals {
+    signals: Vec<SignalDrivers>,
+}
+
+impl ConstantSignals {
+    fn get_mut(&mut self, decl_pos: &SrcPos) -> Option<&mut SignalDrivers> {
+        self.signals
+            .iter_mut()
+            .find(|signal| signal.ident.pos == *decl_pos)
+    }
+
+    fn assign_name(&mut self, name: &Name, literal: bool) {
+        // Assigning a part of the signal leaves the rest of it undriven
+        let literal = literal
+            && if let Name::Designator(..) = name {
+                true
+            } else {
+                false
+            };
+        if let Some(signal) = referenced_signal(name).and_then(|pos| self.get_mut(pos)) {
+            signal.assignments += 1;
+            signal.literal &= literal;
+        }
+    }
+
+    fn assign(&mut self, target: &Target, literal: bool) {
+        match target {
+            Target::Name(ref name) => self.assign_name(name, literal),
+            Target::Aggregate(ref assocs) => {
+                for assoc in assocs.iter() {
+                    let expr = match assoc {
+                        ElementAssociation::Positional(ref expr) => expr,
+                        ElementAssociation::Named(_, ref expr) => expr,
+                    };
+                    if let Expression::Name(ref name) = expr.item {
+                        self.assign_name(name, false);
+                    }
+                }
+            }
+        }
+    }
+
+    fn connect(&mut self, elems: &[AssociationElement]) {
+        for elem in elems.iter() {
+            if let ActualPart::Expression(Expression::Name(ref name)) = elem.actual.item {
+                if let Some(signal) = referenced_signal(name).and_then(|pos| self.get_mut(pos)) {
+                    signal.connected = true;
+                }
+            }
+        }
+    }
+
+    fn sequential(&mut self, statements: &[LabeledSequentialStatement]) {
+        for statement in statements.iter() {
+            match statement.statement {
+                // The value of a sequential assignment depends on when the process runs
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    self.assign(&assign.target.item, false);
+                }
+                SequentialStatement::SignalForceAssignment(ref assign) => {
+                    self.assign(&assign.target.item, false);
+                }
+                SequentialStatement::ProcedureCall(ref pcall) => {
+                    self.connect(&pcall.parameters);
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.sequential(&conditional.item);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.sequential(else_item);
+                    }
+                }
+                SequentialStatement::Case(ref case) => {
+                    for alternative in case.alternatives.iter() {
+                        self.sequential(&alternative.item);
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.sequential(&loop_stmt.statements);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn concurrent(&mut self, statements: &[LabeledConcurrentStatement]) {
+        for statement in statements.iter() {
+            match statement.statement {
+                ConcurrentStatement::Assignment(ref assign) => {
+                    let literal = !assign.guarded && is_literal_waveform(&assign.rhs);
+                    self.assign(&assign.target.item, literal);
+                }
+                ConcurrentStatement::Process(ref process) => {
+                    self.sequential(&process.statements);
+                }
+                ConcurrentStatement::Instance(ref instance) => {
+                    self.connect(&instance.port_map);
+                }
+                ConcurrentStatement::ProcedureCall(ref pcall) => {
+                    self.connect(&pcall.call.parameters);
+                }
+                ConcurrentStatement::Block(ref block) => {
+                    self.concurrent(&block.statements);
+                }
+                ConcurrentStatement::ForGenerate(ref gen) => {
+                    self.concurrent(&gen.body.statements);
+                }
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    for conditional in gen.conditionals.iter() {
+                        self.concurrent(&conditional.item.statements);
+                    }
+                    if let Some(ref else_item) = gen.else_item {
+                        self.concurrent(&else_item.statements);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    for alternative in gen.alternatives.iter() {
+                        self.concurrent(&alternative.item.statements);
+                    }
+                }
+                ConcurrentStatement::Assert(..) => {}
+            }
+        }
+    }
+}
+
+/// The reads and assignments of a signal in the order they appear within a process
+struct DefaultValueReads<'s> {
+    signal: &'s SrcPos,
+    /// The first read before the signal is assigned
+    read: Option<SrcPos>,
+    assigned: bool,
+}
+
+impl<'s> DefaultValueReads<'s> {
+    fn search(&mut self, searchable: &impl Search) {
+        if self.assigned || self.read.is_some() {
+            return;
+        }
+        self.read = FindAllReferences::search(searchable, self.signal)
+            .into_iter()
+            .next();
+    }
+
+    fn is_referenced(&self, searchable: &impl Search) -> bool {
+        !FindAllReferences::search(searchable, self.signal).is_empty()
+    }
+
+    fn rhs<T: Search>(&mut self, rhs: &AssignmentRightHand<T>) {
+        match rhs {
+            AssignmentRightHand::Simple(..) => {}
+            AssignmentRightHand::Conditional(ref conditionals) => {
+                for conditional in conditionals.conditionals.iter() {
+                    self.search(&conditional.condition);
+                }
+            }
+            AssignmentRightHand::Selected(ref selection) => {
+                self.search(&selection.expression);
+            }
+        }
+        for item in rhs.items() {
+            self.search(item);
+        }
+    }
+
+    fn assign(&mut self, target: &WithPos<Target>) {
+        if self.is_referenced(target) {
+            self.assigned = true;
+        }
+    }
+
+    fn sequential(&mut self, statements: &[LabeledSequentialStatement]) {
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::SignalAssignment(ref assign) => {
+                    self.rhs(&assign.rhs);
+                    self.assign(&assign.target);
+                }
+                SequentialStatement::SignalForceAssignment(ref assign) => {
+                    self.rhs(&assign.rhs);
+                    self.assign(&assign.target);
+                }
+                SequentialStatement::VariableAssignment(ref assign) => {
+                    self.rhs(&assign.rhs);
+                }
+                // The signal might be the actual of an out parameter
+                SequentialStatement::ProcedureCall(ref pcall) => {
+                    if self.is_referenced(pcall) {
+                        self.assigned = true;
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.search(&conditional.condition);
+                        self.sequential(&conditional.item);
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.sequential(else_item);
+                    }
+                }
+                SequentialStatement::Case(ref case) => {
+                    self.search(&case.expression);
+                    for alternative in case.alternatives.iter() {
+                        self.sequential(&alternative.item);
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.sequential(&loop_stmt.statements);
+                }
+                _ => self.search(statement),
+            }
+        }
+    }
+}
+
+/// The processes of concurrent statements including those nested in blocks and generates
+fn collect_processes<'s>(
+    statements: &'s [LabeledConcurrentStatement],
+    processes: &mut Vec<&'s ProcessStatement>,
+) {
+    for statement in statements.iter() {
+        match statement.statement {
+            ConcurrentStatement::Process(ref process) => processes.push(process),
+            ConcurrentStatement::Block(ref block) => {
+                collect_processes(&block.statements, processes);
+            }
+            ConcurrentStatement::ForGenerate(ref gen) => {
+                collect_processes(&gen.body.statements, processes);
+            }
+            ConcurrentStatement::IfGenerate(ref gen) => {
+                for conditional in gen.conditionals.iter() {
+                    collect_processes(&conditional.item.statements, processes);
+                }
+                if let Some(ref else_item) = gen.else_item {
+                    collect_processes(&else_item.statements, processes);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(ref gen) => {
+                for alternative in gen.alternatives.iter() {
+                    collect_processes(&alternative.item.statements, processes);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// True if the condition is a rising_edge/falling_edge call or uses the 'event attribute
+    fn is_clock_edge(&self, expr: &Expression) -> bool {
+        ClockEdge::new(|name| self.symbol_utf8(name)).is_clock_edge(expr)
+    }
+
+    /// Check statements within a clock edge for variables that are copied after having
+    /// been assigned, where the assigned value was probably not the intended one
+    fn check_variable_copies(
+        &self,
+        variables: &[SrcPos],
+        assigned: &mut Vec<SrcPos>,
+        statements: &[LabeledSequentialStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                SequentialStatement::VariableAssignment(ref assign) => {
+                    if let AssignmentRightHand::Simple(ref expr) = assign.rhs {
+                        if let Expression::Name(ref name) = expr.item {
+                            let source_var = referenced_designator(name)
+                                .and_then(|designator| designator.reference.as_ref());
+                            if let Some(source_var) = source_var {
+                                if assigned.contains(source_var) {
+                                    diagnostics.push(Diagnostic::hint(
+                                        &expr.pos,
+                                        "Variable is read after being assigned in the same clock edge, the new value is used and not the previous one",
+                                    ));
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(target) = target_reference(&assign.target.item) {
+                        if variables.contains(target) {
+                            assigned.push(target.clone());
+                        }
+                    }
+                }
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.check_variable_copies(
+                            variables,
+                            assigned,
+                            &conditional.item,
+                            diagnostics,
+                        );
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.check_variable_copies(variables, assigned, else_item, diagnostics);
+                    }
+                }
+                SequentialStatement::Case(ref case) => {
+                    for alternative in case.alternatives.iter() {
+                        self.check_variable_copies(
+                            variables,
+                            assigned,
+                            &alternative.item,
+                            diagnostics,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn find_clock_edges(
+        &self,
+        variables: &[SrcPos],
+        statements: &[LabeledSequentialStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for statement in statements.iter() {
+            if let SequentialStatement::If(ref ifstmt) = statement.statement {
+                for conditional in ifstmt.conditionals.iter() {
+                    if self.is_clock_edge(&conditional.condition.item) {
+                        let mut assigned = Vec::new();
+                        self.check_variable_copies(
+                            variables,
+                            &mut assigned,
+                            &conditional.item,
+                            diagnostics,
+                        );
+                    } else {
+                        self.find_clock_edges(variables, &conditional.item, diagnostics);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn lint_variable_as_signal(
+        &self,
+        decl: &[Declaration],
+        statements: &[LabeledSequentialStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut variables = Vec::new();
+        for decl in decl.iter() {
+            if let Declaration::Object(ref object) = decl {
+                if object.class == ObjectClass::Variable {
+                    variables.push(object.ident.pos.clone());
+                }
+            }
+        }
+
+        if !variables.is_empty() {
+            self.find_clock_edges(&variables, statements, diagnostics);
+        }
+    }
+
+    /// Find trivial cases of a subprogram calling itself outside of any conditional statement
+    pub fn lint_unconditional_recursion(
+        &self,
+        body: &SubprogramBody,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let designator = body.specification.designator();
+        let subpgm = CalledSubprogram {
+            designator: &designator.item,
+            decl_pos: &designator.pos,
+            num_params: match body.specification {
+                SubprogramDeclaration::Function(ref function) => function.parameter_list.len(),
+                SubprogramDeclaration::Procedure(ref procedure) => procedure.parameter_list.len(),
+            },
+        };
+
+        for statement in body.statements.iter() {
+            let call_pos = match statement.statement {
+                SequentialStatement::ProcedureCall(ref pcall) => {
+                    if subpgm.is_fcall(pcall) {
+                        Some(&pcall.name.pos)
+                    } else {
+                        None
+                    }
+                }
+                SequentialStatement::VariableAssignment(ref assign) => match assign.rhs {
+                    AssignmentRightHand::Simple(ref expr) => subpgm.find_in_expression(expr),
+                    _ => None,
+                },
+                SequentialStatement::Return(ref ret) => ret
+                    .expression
+                    .as_ref()
+                    .and_then(|expr| subpgm.find_in_expression(expr)),
+                _ => None,
+            };
+
+            if let Some(call_pos) = call_pos {
+                diagnostics.push(Diagnostic::warning(
+                    call_pos,
+                    format!(
+                        "'{}' calls itself unconditionally, the recursion never terminates",
+                        designator.item
+                    ),
+                ));
+                return;
+            }
+
+            match statement.statement {
+                // Statements after the return are never executed
+                SequentialStatement::Return(..) => break,
+                // Statements after a compound statement might be guarded by a return within it
+                SequentialStatement::If(..)
+                | SequentialStatement::Case(..)
+                | SequentialStatement::Loop(..) => break,
+                _ => {}
+            }
+        }
+    }
+
+    fn is_bundled_library(&self) -> bool {
+        BUNDLED_LIBRARIES
+            .iter()
+            .any(|name| *self.work_library_name() == self.symbol_utf8(name))
+    }
+
+    /// Check the identifier of a declaration against the casing convention of its kind
+    pub fn lint_casing(
+        &self,
+        kind: DeclarationKind,
+        ident: &Ident,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        // The convention of the declaration kind itself takes precedence over the
+        // convention of a more general kind
+        let mut casing: Option<&CasingConvention> = None;
+        for lint in self.enabled_lints() {
+            if let Lint::Casing(lint_kind, convention) = lint {
+                if *lint_kind == kind {
+                    casing = Some(convention);
+                    break;
+                } else if lint_kind.includes(kind) {
+                    casing = Some(convention);
+                }
+            }
+        }
+
+        let casing = if let Some(casing) = casing {
+            casing
+        } else {
+            return;
+        };
+
+        if self.is_bundled_library() {
+            return;
+        }
+
+        let name = ident.item.name_utf8();
+        if name.starts_with('\\') {
+            // Extended identifier
+            return;
+        }
+
+        let suggestion = casing.apply(&name);
+        if suggestion != name {
+            diagnostics.push(Diagnostic::warning(
+                &ident.pos,
+                format!(
+                    "{} '{}' does not follow {}, consider renaming to '{}'",
+                    capitalize(kind.describe()),
+                    name,
+                    casing.describe(),
+                    suggestion
+                ),
+            ));
+        }
+    }
+
+    /// Warn when a declaration has the same name as a declaration of a standard package
+    pub fn lint_shadows_standard_name(
+        &self,
+        ident: &Ident,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalNullResult {
+        if !self.is_lint_enabled(Lint::ShadowsStandardName) {
+            return Ok(());
+        }
+
+        if self.is_bundled_library() {
+            // The standard packages themselves
+            return Ok(());
+        }
+
+        let designator = Designator::Identifier(ident.item.clone());
+        for (library, package) in STANDARD_PACKAGES.iter() {
+            let data = if let Some(data) = self.get_primary_analysis(
+                &ident.pos,
+                &self.symbol_utf8(library),
+                &self.symbol_utf8(package),
+                PrimaryKind::Package,
+            ) {
+                data?
+            } else {
+                continue;
+            };
+
+            if data.result().region.lookup_immediate(&designator).is_some() {
+                diagnostics.push(Diagnostic::warning(
+                    &ident.pos,
+                    format!(
+                        "'{}' shadows a declaration of {}.{}",
+                        ident.item, library, package
+                    ),
+                ));
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Suggest constants for architecture signals which are only assigned a literal once
+    /// Signals connected to ports or procedure calls are not considered
+    pub fn lint_signal_could_be_constant(
+        &self,
+        decl: &[Declaration],
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut constants = ConstantSignals {
+            signals: Vec::new(),
+        };
+        for decl in decl.iter() {
+            if let Declaration::Object(ref object) = decl {
+                if object.class == ObjectClass::Signal {
+                    constants.signals.push(SignalDrivers {
+                        ident: object.ident.clone(),
+                        assignments: 0,
+                        literal: true,
+                        connected: false,
+                    });
+                }
+            }
+        }
+
+        if constants.signals.is_empty() {
+            return;
+        }
+
+        constants.concurrent(statements);
+
+        for signal in constants.signals.iter() {
+            if signal.assignments == 1 && signal.literal && !signal.connected {
+                diagnostics.push(Diagnostic::hint(
+                    &signal.ident.pos,
+                    format!(
+                        "Signal '{}' is only assigned a constant value, consider declaring it as a constant",
+                        signal.ident.item
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Warn when an architecture signal without initial value is read but never assigned
+    /// or when a process reads it before assigning it, such as a register without reset
+    /// whose first value is the default
+    pub fn lint_default_initial_value(
+        &self,
+        decl: &[Declaration],
+        statements: &[LabeledConcurrentStatement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let mut processes = Vec::new();
+        collect_processes(statements, &mut processes);
+
+        let mut drivers = ConstantSignals {
+            signals: Vec::new(),
+        };
+        for decl in decl.iter() {
+            if let Declaration::Object(ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ref ident,
+                expression: None,
+                ..
+            }) = decl
+            {
+                drivers.signals.push(SignalDrivers {
+                    ident: ident.clone(),
+                    assignments: 0,
+                    literal: false,
+                    connected: false,
+                });
+            }
+        }
+        drivers.concurrent(statements);
+
+        for signal in drivers.signals.iter() {
+            let ident = &signal.ident;
+
+            // Every reference of a signal which is never driven is a read
+            if signal.assignments == 0 && !signal.connected {
+                let read = statements
+                    .iter()
+                    .flat_map(|statement| FindAllReferences::search(statement, &ident.pos))
+                    .next();
+                if let Some(read) = read {
+                    let mut warning = Diagnostic::warning(
+                        &read,
+                        format!(
+                            "Signal '{}' is read but never assigned and relies on the default initial value",
+                            ident.item
+                        ),
+                    );
+                    warning.add_related(&ident.pos, "Defined here");
+                    diagnostics.push(warning);
+                }
+                continue;
+            }
+
+            for process in processes.iter() {
+                let mut reads = DefaultValueReads {
+                    signal: &ident.pos,
+                    read: None,
+                    assigned: false,
+                };
+                reads.sequential(&process.statements);
+
+                if let (Some(read), true) = (reads.read, reads.assigned) {
+                    let mut warning = Diagnostic::warning(
+                        &read,
+                        format!(
+                            "Signal '{}' is read before being assigned and relies on the default initial value",
+                            ident.item
+                        ),
+                    );
+                    warning.add_related(&ident.pos, "Defined here");
+                    diagnostics.push(warning);
+                }
+            }
+        }
+    }
+
+    /// Inform when a variable or signal is assigned an object of opposite range direction
+    pub fn lint_expr_assignment_direction(
+        &self,
+        region: &Region<'_>,
+        target: &WithPos<Target>,
+        rhs: &AssignmentRightHand<WithPos<Expression>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        self.lint_assignment_direction(region, target, rhs.items(), diagnostics);
+    }
+
+    pub fn lint_waveform_assignment_direction(
+        &self,
+        region: &Region<'_>,
+        target: &WithPos<Target>,
+        rhs: &AssignmentRightHand<Waveform>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let values = rhs
+            .items()
+            .into_iter()
+            .flat_map(|wavf| match wavf {
+                Waveform::Elements(ref elems) => elems.iter().map(|elem| &elem.value).collect(),
+                Waveform::Unaffected => Vec::new(),
+            })
+            .collect();
+        self.lint_assignment_direction(region, target, values, diagnostics);
+    }
+
+    fn lint_assignment_direction(
+        &self,
+        region: &Region<'_>,
+        target: &WithPos<Target>,
+        sources: Vec<&WithPos<Expression>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let name = if let Target::Name(ref name) = target.item {
+            name
+        } else {
+            return;
+        };
+        let designator = match name {
+            Name::Designator(ref designator) => designator,
+            Name::Selected(_, ref suffix) => &suffix.item,
+            _ => return,
+        };
+
+        if let Some(direction) = self.name_direction(region, &target.pos, name) {
+            for source in sources {
+                self.lint_direction_mismatch(
+                    region,
+                    direction,
+                    &format!("'{}'", designator.item),
+                    &source.pos,
+                    &source.item,
+                    diagnostics,
+                );
+            }
+        }
+    }
+
+    /// Inform when an actual of opposite range direction is connected to a port
+    pub fn lint_port_directions(
+        &self,
+        parent: &Region<'_>,
+        entity_region: &Region<'_>,
+        port_map: &[AssociationElement],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for AssociationElement { formal, actual } in port_map.iter() {
+            let formal_name = if let Some(WithPos {
+                item: Name::Designator(ref designator),
+                ..
+            }) = formal
+            {
+                designator.designator()
+            } else {
+                continue;
+            };
+
+            let port_direction = entity_region
+                .lookup_immediate(formal_name)
+                .and_then(|ents| ents.as_non_overloaded())
+                .and_then(|ent| ent.object_subtype())
+                .and_then(|subtype| subtype.direction());
+
+            if let (Some(port_direction), ActualPart::Expression(ref expr)) =
+                (port_direction, &actual.item)
+            {
+                self.lint_direction_mismatch(
+                    parent,
+                    port_direction,
+                    &format!("port '{}'", formal_name),
+                    &actual.pos,
+                    expr,
+                    diagnostics,
+                );
+            }
+        }
+    }
+
+    fn lint_direction_mismatch(
+        &self,
+        region: &Region<'_>,
+        target_direction: Direction,
+        target: &str,
+        source_pos: &SrcPos,
+        source: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let source_direction = if let Expression::Name(ref name) = source {
+            self.name_direction(region, source_pos, name)
+        } else {
+            None
+        };
+
+        if let Some(source_direction) = source_direction {
+            if source_direction != target_direction {
+                diagnostics.push(Diagnostic::info(
+                    source_pos,
+                    format!(
+                        "Range direction '{}' does not match direction '{}' of {}",
+                        describe_direction(source_direction),
+                        describe_direction(target_direction),
+                        target
+                    ),
+                ));
+            }
+        }
+    }
+}
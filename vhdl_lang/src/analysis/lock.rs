@@ -48,6 +48,11 @@ impl<T, R> AnalysisLock<T, R> {
         RwLockReadGuard::map(self.state.read(), |data| &data.data)
     }
 
+    /// Returns true if the analysis result is available.
+    pub fn is_analyzed(&self) -> bool {
+        self.state.read().result.is_some()
+    }
+
     /// Reset analysis state, analysis needs to be redone.
     pub fn reset(&self) {
         let mut guard = self.state.write();
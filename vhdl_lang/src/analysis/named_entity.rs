@@ -21,16 +21,19 @@ pub enum NamedEntityKind {
     SubprogramDecl(Signature),
     Subprogram(Signature),
     EnumLiteral(Signature),
-    // An optional list of implicit declarations
+    // The class of the type and an optional list of implicit declarations
     // Use Weak reference since implicit declaration typically reference the type itself
-    TypeDeclaration(Vec<Weak<NamedEntity>>),
+    TypeDeclaration(Type, Vec<Weak<NamedEntity>>),
     Subtype(Subtype),
     IncompleteType,
     InterfaceType,
     Label,
-    Object(ObjectClass),
+    // The subtype is None when it could not be resolved
+    // The value is only known for constants with a static integer initial value
+    Object(ObjectClass, Option<Subtype>, Option<i64>),
     InterfaceObject(InterfaceObject),
-    PhysicalLiteral,
+    // The physical type of the literal
+    PhysicalLiteral(Arc<NamedEntity>),
     DeferredConstant,
     // The region of the protected type which needs to be extendend by the body
     ProtectedType(Arc<Region<'static>>),
@@ -44,12 +47,86 @@ pub enum NamedEntityKind {
     LocalPackageInstance(Arc<Region<'static>>),
 }
 
+/// The kind of a declaration at the level of detail needed to choose an icon or action
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SymbolKind {
+    Constant,
+    Signal,
+    Variable,
+    File,
+    Type,
+    Subtype,
+    Function,
+    Procedure,
+    EnumLiteral,
+    PhysicalLiteral,
+    Alias,
+    RecordField,
+    Component,
+    Attribute,
+    Label,
+    Library,
+    Entity,
+    Configuration,
+    Package,
+    Context,
+}
+
+impl From<ObjectClass> for SymbolKind {
+    fn from(class: ObjectClass) -> SymbolKind {
+        match class {
+            ObjectClass::Constant => SymbolKind::Constant,
+            ObjectClass::Signal => SymbolKind::Signal,
+            ObjectClass::Variable | ObjectClass::SharedVariable => SymbolKind::Variable,
+        }
+    }
+}
+
 impl NamedEntityKind {
-    pub fn from_object_declaration(decl: &ObjectDeclaration) -> NamedEntityKind {
+    pub fn symbol_kind(&self) -> SymbolKind {
+        use NamedEntityKind::*;
+        match self {
+            AliasOf(..) | OtherAlias => SymbolKind::Alias,
+            File | InterfaceFile(..) => SymbolKind::File,
+            RecordField => SymbolKind::RecordField,
+            Component => SymbolKind::Component,
+            Attribute => SymbolKind::Attribute,
+            SubprogramDecl(signature) | Subprogram(signature) => {
+                if signature.return_type.is_some() {
+                    SymbolKind::Function
+                } else {
+                    SymbolKind::Procedure
+                }
+            }
+            EnumLiteral(..) => SymbolKind::EnumLiteral,
+            TypeDeclaration(..) | IncompleteType | InterfaceType | ProtectedType(..) => {
+                SymbolKind::Type
+            }
+            Subtype(..) => SymbolKind::Subtype,
+            Label => SymbolKind::Label,
+            Object(class, ..) => SymbolKind::from(*class),
+            InterfaceObject(object) => SymbolKind::from(object.class),
+            PhysicalLiteral(..) => SymbolKind::PhysicalLiteral,
+            DeferredConstant => SymbolKind::Constant,
+            Library => SymbolKind::Library,
+            Entity(..) => SymbolKind::Entity,
+            Configuration(..) => SymbolKind::Configuration,
+            Package(..) | UninstPackage(..) | PackageInstance(..) | LocalPackageInstance(..) => {
+                SymbolKind::Package
+            }
+            Context(..) => SymbolKind::Context,
+        }
+    }
+
+    pub fn from_object_declaration(
+        decl: &ObjectDeclaration,
+        subtype: Option<Subtype>,
+        value: Option<i64>,
+    ) -> NamedEntityKind {
         if decl.class == ObjectClass::Constant && decl.expression.is_none() {
             NamedEntityKind::DeferredConstant
         } else {
-            NamedEntityKind::Object(decl.class)
+            NamedEntityKind::Object(decl.class, subtype, value)
         }
     }
 
@@ -62,7 +139,7 @@ impl NamedEntityKind {
     }
 
     pub fn is_non_deferred_constant(&self) -> bool {
-        if let NamedEntityKind::Object(ObjectClass::Constant) = self {
+        if let NamedEntityKind::Object(ObjectClass::Constant, ..) = self {
             true
         } else {
             false
@@ -89,7 +166,7 @@ impl NamedEntityKind {
     }
 
     pub fn implicit_declarations(&self) -> Vec<Arc<NamedEntity>> {
-        if let NamedEntityKind::TypeDeclaration(ref implicit) = self {
+        if let NamedEntityKind::TypeDeclaration(_, ref implicit) = self {
             implicit
                 .iter()
                 .map(|ent|
@@ -108,7 +185,7 @@ impl NamedEntityKind {
             OtherAlias => "alias",
             File => "file",
             InterfaceFile(..) => "file",
-            RecordField => "file",
+            RecordField => "record field",
             Component => "file",
             Attribute => "file",
             SubprogramDecl(signature) | Subprogram(signature) => {
@@ -124,9 +201,9 @@ impl NamedEntityKind {
             IncompleteType => "type",
             InterfaceType => "type",
             Label => "label",
-            Object(class) => class.describe(),
+            Object(class, ..) => class.describe(),
             InterfaceObject(object) => object.class.describe(),
-            PhysicalLiteral => "physical literal",
+            PhysicalLiteral(..) => "physical literal",
             DeferredConstant => "deferred constant",
             ProtectedType(..) => "protected type",
             Library => "library",
@@ -147,29 +224,140 @@ impl std::fmt::Debug for NamedEntityKind {
     }
 }
 
+/// The class of a type declaration
+#[derive(Clone)]
+pub enum Type {
+    /// The enumeration literals are the implicit declarations of the type
+    Enum,
+    /// The low and high bound of the declared range when they are static
+    Integer(Option<(i64, i64)>),
+    Real,
+    Physical,
+    /// The element subtype is None when it could not be resolved
+    Array(Option<Subtype>),
+    /// The fields in the order they are declared
+    Record(Vec<Arc<NamedEntity>>),
+    Access,
+    File,
+    /// A protected type before the region of the declaration is known
+    Protected,
+}
+
 /// Signals, (shared) variables and constants
 #[derive(Clone)]
 pub struct InterfaceObject {
     pub class: ObjectClass,
     pub mode: Mode,
-    pub subtype: Subtype,
+    /// None when the subtype indication could not be resolved
+    pub subtype: Option<Subtype>,
     pub has_default: bool,
 }
 
 #[derive(Clone)]
 pub struct Subtype {
     base: Arc<NamedEntity>,
+    // The number of elements of a one dimensional array constrained by a static range
+    length: Option<u64>,
+    // The direction of the static range of a one dimensional array
+    direction: Option<Direction>,
+    // The low and high bound of a static range constraint of a scalar subtype
+    range: Option<(i64, i64)>,
+    // True if the subtype indication has a resolution indication
+    resolved: bool,
 }
 
 impl Subtype {
     pub fn new(base: Arc<NamedEntity>) -> Subtype {
+        Self::with_length(base, None)
+    }
+
+    pub fn with_length(base: Arc<NamedEntity>, length: Option<u64>) -> Subtype {
         debug_assert!(base.actual_kind().is_type());
-        Subtype { base }
+        Subtype {
+            base,
+            length,
+            direction: None,
+            range: None,
+            resolved: false,
+        }
+    }
+
+    pub fn with_resolution(self, resolved: bool) -> Subtype {
+        Subtype { resolved, ..self }
+    }
+
+    pub fn with_direction(self, direction: Option<Direction>) -> Subtype {
+        Subtype { direction, ..self }
+    }
+
+    pub fn with_range(self, range: Option<(i64, i64)>) -> Subtype {
+        Subtype { range, ..self }
     }
 
     pub fn base(&self) -> &Arc<NamedEntity> {
         &self.base
     }
+
+    /// The statically known number of elements, either from the constraint of this subtype
+    /// or from the named subtype it refers to
+    pub fn length(&self) -> Option<u64> {
+        if self.length.is_some() {
+            return self.length;
+        }
+
+        if let NamedEntityKind::Subtype(ref subtype) = self.base.actual_kind() {
+            subtype.length()
+        } else {
+            None
+        }
+    }
+
+    /// The direction of the static range, either from the constraint of this subtype
+    /// or from the named subtype it refers to
+    pub fn direction(&self) -> Option<Direction> {
+        if self.direction.is_some() {
+            return self.direction;
+        }
+
+        if let NamedEntityKind::Subtype(ref subtype) = self.base.actual_kind() {
+            subtype.direction()
+        } else {
+            None
+        }
+    }
+
+    /// The low and high bound of the static range, either from the constraint of this
+    /// subtype or from the named subtype or integer type it refers to
+    pub fn range(&self) -> Option<(i64, i64)> {
+        if self.range.is_some() {
+            return self.range;
+        }
+        self.base.static_range()
+    }
+
+    /// True if the subtype is known to be unresolved, neither having a resolution function
+    /// itself nor through the named subtype it refers to or the element subtype of an array
+    /// Records are never known to be unresolved since their elements are not tracked
+    pub fn is_unresolved(&self) -> bool {
+        if self.resolved {
+            return false;
+        }
+
+        match self.base.actual_kind() {
+            NamedEntityKind::Subtype(ref subtype) => subtype.is_unresolved(),
+            NamedEntityKind::TypeDeclaration(ref class, ..) => match class {
+                Type::Array(Some(ref elem)) => elem.is_unresolved(),
+                Type::Array(None) | Type::Record(..) | Type::Protected => false,
+                Type::Enum
+                | Type::Integer(..)
+                | Type::Real
+                | Type::Physical
+                | Type::Access
+                | Type::File => true,
+            },
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -189,6 +377,18 @@ impl ParameterList {
     }
 }
 
+/// The base type of a parameter which is always an InterfaceObject with a known subtype or an InterfaceFile
+fn param_type(param: &NamedEntity) -> &NamedEntity {
+    match param.kind() {
+        NamedEntityKind::InterfaceObject(InterfaceObject {
+            subtype: Some(subtype),
+            ..
+        }) => subtype.base().base_type(),
+        NamedEntityKind::InterfaceFile(file_type) => file_type.base_type(),
+        _ => unreachable!(),
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct Signature {
     /// Vector of InterfaceObject or InterfaceFile
@@ -207,18 +407,51 @@ impl Signature {
         }
     }
 
+    /// The base types of the parameters
+    pub fn param_types(&self) -> Vec<&NamedEntity> {
+        self.params
+            .params
+            .iter()
+            .map(|ent| param_type(ent))
+            .collect()
+    }
+
+    /// True if a call with the number of positional arguments is possible since all
+    /// remaining parameters have default values
+    pub fn can_be_called_with_arity(&self, count: usize) -> bool {
+        let params = &self.params.params;
+        count <= params.len()
+            && params[count..].iter().all(|param| match param.kind() {
+                NamedEntityKind::InterfaceObject(obj) => obj.has_default,
+                _ => false,
+            })
+    }
+
+    /// True if a parameter is a signal of mode out, inout or buffer
+    /// A procedure which is not within a process can drive no other signals
+    pub fn drives_signals(&self) -> bool {
+        self.params.params.iter().any(|param| match param.kind() {
+            NamedEntityKind::InterfaceObject(obj) => {
+                obj.class == ObjectClass::Signal
+                    && match obj.mode {
+                        Mode::Out | Mode::InOut | Mode::Buffer => true,
+                        _ => false,
+                    }
+            }
+            _ => false,
+        })
+    }
+
+    pub fn return_type(&self) -> Option<&Arc<NamedEntity>> {
+        self.return_type.as_ref()
+    }
+
     pub fn key(&self) -> SignatureKey {
         let params = self
             .params
             .params
             .iter()
-            .map(|ent| match ent.kind() {
-                NamedEntityKind::InterfaceObject(obj) => obj.subtype.base().base_type().id(),
-                NamedEntityKind::InterfaceFile(file_type) => file_type.base_type().id(),
-                _ => {
-                    unreachable!();
-                }
-            })
+            .map(|ent| param_type(ent).id())
             .collect();
         let return_type = self.return_type.as_ref().map(|ent| ent.base_type().id());
 
@@ -232,11 +465,7 @@ impl Signature {
         let mut result = String::new();
         result.push('[');
         for (i, param) in self.params.params.iter().enumerate() {
-            let type_ent = match param.kind() {
-                NamedEntityKind::InterfaceObject(obj) => obj.subtype.base().base_type(),
-                NamedEntityKind::InterfaceFile(file_type) => file_type.base_type(),
-                _ => unreachable!(),
-            };
+            let type_ent = param_type(param);
             result.push_str(&type_ent.designator().to_string());
 
             if i + 1 < self.params.params.len() || self.return_type.is_some() {
@@ -445,6 +674,33 @@ impl NamedEntity {
         }
     }
 
+    /// The class of the base type when this is a type
+    /// The low and high bound of the static range of a scalar type or subtype
+    pub fn static_range(&self) -> Option<(i64, i64)> {
+        match self.actual_kind() {
+            NamedEntityKind::Subtype(ref subtype) => subtype.range(),
+            NamedEntityKind::TypeDeclaration(Type::Integer(range), ..) => *range,
+            _ => None,
+        }
+    }
+
+    pub fn base_type_class(&self) -> Option<&Type> {
+        if let NamedEntityKind::TypeDeclaration(ref class, _) = self.base_type().kind() {
+            Some(class)
+        } else {
+            None
+        }
+    }
+
+    /// The subtype of an object or interface object when it is known
+    pub fn object_subtype(&self) -> Option<&Subtype> {
+        match self.actual_kind() {
+            NamedEntityKind::Object(_, ref subtype, _) => subtype.as_ref(),
+            NamedEntityKind::InterfaceObject(ref object) => object.subtype.as_ref(),
+            _ => None,
+        }
+    }
+
     /// Strip aliases and return reference to actual entity kind
     pub fn actual_kind(&self) -> &NamedEntityKind {
         self.as_actual().kind()
@@ -0,0 +1,277 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use crate::ast::*;
+use crate::data::*;
+
+/// A reset inferred from the leading if statement of a process
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ResetInfo {
+    pub signal: Symbol,
+    /// True if the reset is active when the signal is '1'
+    pub active_high: bool,
+    /// True if the reset is checked before the clock edge
+    pub asynchronous: bool,
+}
+
+/// A process statement together with its inferred clock and reset
+#[derive(PartialEq, Clone, Debug)]
+pub struct ProcessInfo {
+    /// The architecture containing the process
+    pub architecture: UnitId,
+    pub label: Option<Ident>,
+    pub sensitivity_list: Option<SensitivityList>,
+    /// The signal of a rising_edge/falling_edge call or 'event attribute
+    pub clock: Option<Symbol>,
+    pub reset: Option<ResetInfo>,
+}
+
+/// Recognizes clock edge conditions
+pub(super) struct ClockEdge {
+    rising_edge: Symbol,
+    falling_edge: Symbol,
+    event: Symbol,
+}
+
+fn simple_name(name: &Name) -> Option<&Symbol> {
+    if let Name::Designator(ref designator) = name {
+        if let Designator::Identifier(ref sym) = designator.item {
+            return Some(sym);
+        }
+    }
+    None
+}
+
+/// The simple name or the suffix of a selected name such as ieee.std_logic_1164.rising_edge
+fn suffix_name(name: &Name) -> Option<&Symbol> {
+    if let Name::Selected(_, ref suffix) = name {
+        if let Designator::Identifier(ref sym) = suffix.item.item {
+            return Some(sym);
+        }
+        None
+    } else {
+        simple_name(name)
+    }
+}
+
+fn simple_name_expression(expr: &Expression) -> Option<&Symbol> {
+    if let Expression::Name(ref name) = expr {
+        simple_name(name)
+    } else {
+        None
+    }
+}
+
+impl ClockEdge {
+    pub fn new(symbol_utf8: impl Fn(&str) -> Symbol) -> ClockEdge {
+        ClockEdge {
+            rising_edge: symbol_utf8("rising_edge"),
+            falling_edge: symbol_utf8("falling_edge"),
+            event: symbol_utf8("event"),
+        }
+    }
+
+    /// Find a rising_edge/falling_edge call or a use of the 'event attribute within the condition
+    /// Returns the name of the clock signal
+    pub fn find<'e>(&self, expr: &'e Expression) -> Option<&'e Name> {
+        match expr {
+            Expression::Binary(_, ref left, ref right) => {
+                self.find(&left.item).or_else(|| self.find(&right.item))
+            }
+            Expression::Unary(_, ref expr) => self.find(&expr.item),
+            Expression::Name(ref name) => match name.as_ref() {
+                Name::FunctionCall(ref fcall) => {
+                    let is_edge = suffix_name(&fcall.name.item)
+                        .map(|sym| *sym == self.rising_edge || *sym == self.falling_edge)
+                        .unwrap_or(false);
+
+                    if !is_edge {
+                        return None;
+                    }
+
+                    if let Some(AssociationElement {
+                        actual:
+                            WithPos {
+                                item: ActualPart::Expression(Expression::Name(ref name)),
+                                ..
+                            },
+                        ..
+                    }) = fcall.parameters.first()
+                    {
+                        Some(name.as_ref())
+                    } else {
+                        None
+                    }
+                }
+                Name::Attribute(ref attr) if attr.attr.item == self.event => Some(&attr.name.item),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn is_clock_edge(&self, expr: &Expression) -> bool {
+        self.find(expr).is_some()
+    }
+
+    /// A reset condition such as rst = '1', rst = '0', rst or not rst
+    fn reset_condition(&self, expr: &Expression) -> Option<(Symbol, bool)> {
+        match expr {
+            Expression::Binary(Binary::EQ, ref left, ref right) => {
+                let (name, value) = match (&left.item, &right.item) {
+                    (name, Expression::Literal(Literal::Character(value)))
+                    | (Expression::Literal(Literal::Character(value)), name) => (name, *value),
+                    _ => return None,
+                };
+                let signal = simple_name_expression(name)?;
+                match value {
+                    b'1' => Some((signal.clone(), true)),
+                    b'0' => Some((signal.clone(), false)),
+                    _ => None,
+                }
+            }
+            Expression::Unary(Unary::Not, ref expr) => {
+                simple_name_expression(&expr.item).map(|signal| (signal.clone(), false))
+            }
+            Expression::Name(..) => {
+                simple_name_expression(expr).map(|signal| (signal.clone(), true))
+            }
+            _ => None,
+        }
+    }
+
+    /// Infer the clock and reset of a process from its leading statement
+    pub fn infer(
+        &self,
+        statements: &[LabeledSequentialStatement],
+    ) -> (Option<Symbol>, Option<ResetInfo>) {
+        let statement = if let Some(statement) = statements.first() {
+            &statement.statement
+        } else {
+            return (None, None);
+        };
+
+        match statement {
+            SequentialStatement::Wait(ref wait) => {
+                let clock = wait
+                    .condition_clause
+                    .as_ref()
+                    .and_then(|cond| self.find(&cond.item))
+                    .and_then(simple_name)
+                    .cloned();
+                let reset = statements.get(1).and_then(|statement| {
+                    if let SequentialStatement::If(ref ifstmt) = statement.statement {
+                        self.synchronous_reset(ifstmt)
+                    } else {
+                        None
+                    }
+                });
+                (clock, reset)
+            }
+            SequentialStatement::If(ref ifstmt) => {
+                let first = if let Some(first) = ifstmt.conditionals.first() {
+                    first
+                } else {
+                    return (None, None);
+                };
+
+                if let Some(clock) = self.find(&first.condition.item) {
+                    let reset = first.item.first().and_then(|statement| {
+                        if let SequentialStatement::If(ref ifstmt) = statement.statement {
+                            self.synchronous_reset(ifstmt)
+                        } else {
+                            None
+                        }
+                    });
+                    (simple_name(clock).cloned(), reset)
+                } else if let Some((signal, active_high)) =
+                    self.reset_condition(&first.condition.item)
+                {
+                    let clock = ifstmt.conditionals[1..]
+                        .iter()
+                        .find_map(|conditional| self.find(&conditional.condition.item))
+                        .and_then(simple_name)
+                        .cloned();
+
+                    if clock.is_some() {
+                        let reset = ResetInfo {
+                            signal,
+                            active_high,
+                            asynchronous: true,
+                        };
+                        (clock, Some(reset))
+                    } else {
+                        (None, None)
+                    }
+                } else {
+                    (None, None)
+                }
+            }
+            _ => (None, None),
+        }
+    }
+
+    fn synchronous_reset(&self, ifstmt: &IfStatement) -> Option<ResetInfo> {
+        let first = ifstmt.conditionals.first()?;
+        let (signal, active_high) = self.reset_condition(&first.condition.item)?;
+        Some(ResetInfo {
+            signal,
+            active_high,
+            asynchronous: false,
+        })
+    }
+
+    /// Collect the processes of the concurrent statements, including nested blocks and generates
+    pub fn collect_processes(
+        &self,
+        architecture: &UnitId,
+        statements: &[LabeledConcurrentStatement],
+        processes: &mut Vec<ProcessInfo>,
+    ) {
+        for statement in statements.iter() {
+            match statement.statement {
+                ConcurrentStatement::Process(ref process) => {
+                    let (clock, reset) = self.infer(&process.statements);
+                    processes.push(ProcessInfo {
+                        architecture: architecture.clone(),
+                        label: statement.label.clone(),
+                        sensitivity_list: process.sensitivity_list.clone(),
+                        clock,
+                        reset,
+                    });
+                }
+                ConcurrentStatement::Block(ref block) => {
+                    self.collect_processes(architecture, &block.statements, processes);
+                }
+                ConcurrentStatement::ForGenerate(ref gen) => {
+                    self.collect_processes(architecture, &gen.body.statements, processes);
+                }
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    for conditional in gen.conditionals.iter() {
+                        self.collect_processes(
+                            architecture,
+                            &conditional.item.statements,
+                            processes,
+                        );
+                    }
+                    if let Some(ref else_item) = gen.else_item {
+                        self.collect_processes(architecture, &else_item.statements, processes);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    for alternative in gen.alternatives.iter() {
+                        self.collect_processes(
+                            architecture,
+                            &alternative.item.statements,
+                            processes,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -269,9 +269,37 @@ impl<'a> Region<'a> {
         }
     }
 
+    /// Subprograms declared in the package or its body must have a body within the package body
+    fn check_subprograms_have_body(&self, diagnostics: &mut dyn DiagnosticHandler) {
+        if self.kind != RegionKind::PackageBody {
+            return;
+        }
+
+        for ent in self.entities.values() {
+            if let NamedEntities::Overloaded(ref overloaded) = ent {
+                for ent in overloaded.entities() {
+                    if let NamedEntityKind::SubprogramDecl(ref signature) = ent.kind() {
+                        if ent.is_explicit() {
+                            ent.error(
+                                diagnostics,
+                                format!(
+                                    "Missing body for {} '{}' with signature {}",
+                                    ent.kind().describe(),
+                                    ent.designator(),
+                                    signature.describe()
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     pub fn close(&mut self, diagnostics: &mut dyn DiagnosticHandler) {
         self.check_deferred_constant_pairs(diagnostics);
         self.check_protected_types_have_body(diagnostics);
+        self.check_subprograms_have_body(diagnostics);
     }
 
     pub fn add_protected_body(&mut self, ident: Ident, diagnostics: &mut dyn DiagnosticHandler) {
@@ -416,6 +444,14 @@ impl<'a> Region<'a> {
             .add_context_visibility(visible_pos, &region.visibility);
     }
 
+    /// All named entities declared immediately within this region
+    pub fn immediates(&self) -> impl Iterator<Item = &Arc<NamedEntity>> {
+        self.entities.values().flat_map(|ents| match ents {
+            NamedEntities::Single(ent) => vec![ent],
+            NamedEntities::Overloaded(overloaded) => overloaded.entities().collect(),
+        })
+    }
+
     /// Lookup a named entity declared in this region
     pub fn lookup_immediate(&self, designator: &Designator) -> Option<&NamedEntities> {
         self.entities.get(designator)
@@ -453,6 +489,44 @@ impl<'a> Region<'a> {
         visible.into_unambiguous(pos, designator)
     }
 
+    /// Lookup overloaded named entities declared in this region or an enclosing region or
+    /// made potentially visible via use clauses
+    /// An inner declaration hides outer declarations and potentially visible declarations
+    /// with the same signature
+    fn lookup_overloaded(&self, pos: &SrcPos, designator: &Designator) -> NamedEntities {
+        let mut entities: Vec<Arc<NamedEntity>> = Vec::new();
+        let mut keys: Vec<SignatureKey> = Vec::new();
+        let mut add = |overloaded: &OverloadedName| {
+            for ent in overloaded.entities() {
+                let key = ent.signature().unwrap().key();
+                if !keys.contains(&key) {
+                    keys.push(key);
+                    entities.push(ent.clone());
+                }
+            }
+        };
+
+        let mut region = Some(self);
+        while let Some(current) = region {
+            match current.lookup_immediate(designator) {
+                Some(NamedEntities::Overloaded(overloaded)) => add(overloaded),
+                // A non-overloaded declaration is hidden by the inner overloaded ones and
+                // hides everything else
+                Some(NamedEntities::Single(..)) => return NamedEntities::new_overloaded(entities),
+                None => {}
+            }
+            region = current.parent;
+        }
+
+        if let Ok(Some(NamedEntities::Overloaded(ref overloaded))) =
+            self.lookup_visible(pos, designator)
+        {
+            add(overloaded);
+        }
+
+        NamedEntities::new_overloaded(entities)
+    }
+
     /// Lookup where this region is the prefix of a selected name
     /// Thus any visibility inside the region is irrelevant
     pub fn lookup_selected(&self, designator: &Designator) -> Option<&NamedEntities> {
@@ -467,7 +541,10 @@ impl<'a> Region<'a> {
         designator: &Designator,
     ) -> Result<NamedEntities, Diagnostic> {
         let result = if let Some(visible) = self.lookup_enclosing(designator) {
-            Some(visible.clone())
+            match visible {
+                NamedEntities::Single(..) => Some(visible.clone()),
+                NamedEntities::Overloaded(..) => Some(self.lookup_overloaded(pos, designator)),
+            }
         } else {
             self.lookup_visible(pos, designator)?
         };
@@ -8,13 +8,20 @@ use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
 
 use super::analyze::*;
+use super::configurations::{block_spec_identifier, collect_bindings, ConfigInfo};
+use super::lint::Lint;
 use super::lock::*;
+use super::named_entity::SymbolKind;
+use super::processes::{ClockEdge, ProcessInfo};
 use super::region::*;
+use super::types::TypeInfo;
 use crate::ast::search::*;
 use crate::ast::*;
 use crate::data::*;
-use crate::syntax::Symbols;
+use crate::syntax::{source_comments, Symbols, VHDLParser};
 use parking_lot::RwLock;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 /// A design unit with design unit data
@@ -22,10 +29,28 @@ pub(super) struct AnalysisData {
     pub diagnostics: Vec<Diagnostic>,
     pub has_circular_dependency: bool,
 
+    pub region: Arc<Region<'static>>,
+
     // Only for primary units
     pub root_region: Arc<Region<'static>>,
-    pub region: Arc<Region<'static>>,
     pub ent: Option<Arc<NamedEntity>>,
+
+    // Regions nested in the design unit such as those of processes and subprograms
+    pub local_regions: Vec<Region<'static>>,
+
+    // Declarations and references of the design unit when indexed during its analysis
+    pub index: Option<IndexData>,
+}
+
+impl AnalysisData {
+    /// The design unit and the declarations of its region and of all nested regions
+    pub fn declarations(&self) -> impl Iterator<Item = &Arc<NamedEntity>> {
+        self.ent.iter().chain(self.region.immediates()).chain(
+            self.local_regions
+                .iter()
+                .flat_map(|region| region.immediates()),
+        )
+    }
 }
 
 pub(super) type UnitReadGuard<'a> = ReadGuard<'a, AnyDesignUnit, AnalysisData>;
@@ -83,6 +108,8 @@ struct Library {
 
     units: FnvHashMap<UnitKey, LockedUnit>,
     units_by_source: FnvHashMap<Source, FnvHashSet<UnitId>>,
+    /// All sources of the library including those without design units
+    sources: FnvHashSet<Source>,
 
     /// Units removed since last analysis.
     removed: FnvHashSet<UnitId>,
@@ -106,6 +133,7 @@ impl<'a> Library {
             ent,
             units: FnvHashMap::default(),
             units_by_source: FnvHashMap::default(),
+            sources: FnvHashSet::default(),
             added: FnvHashSet::default(),
             removed: FnvHashSet::default(),
             duplicates: Vec::new(),
@@ -117,6 +145,7 @@ impl<'a> Library {
     }
 
     fn add_design_unit(&mut self, unit: LockedUnit) {
+        self.sources.insert(unit.source().clone());
         let unit_id = unit.unit_id().clone();
         match self.units.entry(unit.key().clone()) {
             Entry::Occupied(entry) => {
@@ -197,6 +226,7 @@ impl<'a> Library {
             }
         });
         self.units_by_source.remove(source);
+        self.sources.remove(source);
         self.duplicates
             .retain(|(_, value)| value.source() != source);
 
@@ -236,6 +266,75 @@ impl<'a> Library {
     }
 }
 
+/// A member of a package declaration and how many times it is referenced from
+/// design units other than the package and its body
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemberUsage {
+    pub designator: Designator,
+    pub decl_pos: SrcPos,
+    pub external_references: usize,
+}
+
+/// A change of the analysis state of a design unit during incremental analysis
+/// Lets clients keep their own caches in sync with the analysis
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnalysisEvent {
+    /// The design unit was added, removed or changed
+    UnitInvalidated(UnitId),
+    /// The analysis of the design unit was reset since a design unit it depends on was
+    /// invalidated, its references to other design units are cleared until analyzed again
+    ReferencesCleared(UnitId),
+    /// The design unit was analyzed
+    UnitAnalyzed(UnitId),
+}
+
+/// The files which must be processed again after an edit of a file
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditImpact {
+    /// The files which must be parsed again, only the edited file itself
+    pub reparse: Vec<Source>,
+    /// The other files which must be analyzed again since they contain dependent design units
+    pub reanalyze: Vec<Source>,
+}
+
+/// The information about a referenced declaration shown when hovering the reference
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tooltip {
+    /// The position of the declaration
+    pub decl_pos: SrcPos,
+    /// The kind and name of the declaration including the signature of subprograms
+    pub signature: String,
+    /// The type mark of an object or the return type of a function
+    pub type_info: Option<TypeInfo>,
+    /// The text of the comment lines directly before the declaration
+    pub doc: Option<String>,
+}
+
+/// A declaration which already has the new name of a renamed declaration in a design unit
+/// where the renamed declaration is declared or referenced
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenameConflict {
+    /// The first position of the renamed declaration or a reference to it in the design unit
+    pub pos: SrcPos,
+    /// The declaration with the new name
+    pub decl_pos: SrcPos,
+}
+
+/// The positions to edit for a rename together with the conflicts the rename would cause
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenamePreview {
+    pub edits: Vec<SrcPos>,
+    pub conflicts: Vec<RenameConflict>,
+}
+
+/// An enumeration type declared in a design unit with its literals in declaration order
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumInfo {
+    pub designator: Designator,
+    pub decl_pos: SrcPos,
+    pub literals: Vec<Designator>,
+}
+
 /// Contains the entire design state.
 ///
 /// Besides all loaded libraries and design units, `DesignRoot` also keeps track of
@@ -254,6 +353,12 @@ pub struct DesignRoot {
     // Tracks which units have a "use library.all;" clause.
     // library name  =>  set(affected)
     users_of_library_all: RwLock<FnvHashMap<Symbol, FnvHashSet<UnitId>>>,
+
+    // Opt-in lints which are checked during analysis
+    lints: FnvHashSet<Lint>,
+
+    // True if each design unit is indexed at the end of its analysis
+    index_during_analysis: bool,
 }
 
 impl DesignRoot {
@@ -264,7 +369,34 @@ impl DesignRoot {
             users_of: RwLock::new(FnvHashMap::default()),
             missing_primary: RwLock::new(FnvHashMap::default()),
             users_of_library_all: RwLock::new(FnvHashMap::default()),
+            lints: FnvHashSet::default(),
+            index_during_analysis: false,
+        }
+    }
+
+    /// Enable an opt-in lint, all design units are re-analyzed
+    /// A casing convention replaces any previous convention of the same declaration kind
+    pub fn enable_lint(&mut self, lint: Lint) {
+        if self.lints.contains(&lint) {
+            return;
+        }
+
+        if let Lint::Casing(kind, _) = lint {
+            self.lints.retain(|enabled| match enabled {
+                Lint::Casing(enabled_kind, _) => *enabled_kind != kind,
+                _ => true,
+            });
         }
+        self.lints.insert(lint);
+        self.reset_all();
+    }
+
+    pub(super) fn is_lint_enabled(&self, lint: Lint) -> bool {
+        self.lints.contains(&lint)
+    }
+
+    pub(super) fn enabled_lints(&self) -> impl Iterator<Item = &Lint> {
+        self.lints.iter()
     }
 
     /// Create library if it does not exist or return existing
@@ -301,11 +433,48 @@ impl DesignRoot {
             .add_design_file(design_file);
     }
 
+    /// Add a source to the library even if it has no design units such as a file of comments
+    pub fn add_source(&mut self, library_name: Symbol, source: &Source) {
+        self.get_or_create_library(library_name)
+            .sources
+            .insert(source.clone());
+    }
+
     pub fn remove_source(&mut self, library_name: Symbol, source: &Source) {
         self.get_or_create_library(library_name)
             .remove_source(source);
     }
 
+    /// Replace the design units of the std library with the ones parsed from the sources
+    /// such as a vendor specific variant of the standard library
+    /// The standard library of the installation is otherwise added like any other library
+    pub fn add_standard_library_from(
+        &mut self,
+        sources: &[Source],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let parser = VHDLParser {
+            symbols: self.symbols.clone(),
+        };
+        let std_sym = self.symbol_utf8("std");
+        let library = self.get_or_create_library(std_sym);
+
+        let mut previous: Vec<Source> = library.units_by_source.keys().cloned().collect();
+        for (_, unit) in library.duplicates.iter() {
+            if !previous.contains(unit.source()) {
+                previous.push(unit.source().clone());
+            }
+        }
+        for source in previous.iter() {
+            library.remove_source(source);
+        }
+
+        for source in sources.iter() {
+            let design_file = parser.parse_design_source(source, diagnostics);
+            library.add_design_file(design_file);
+        }
+    }
+
     /// Search for reference at position
     /// Character offset on a line in a document (zero-based). Assuming that the line is
     /// represented as a string, the `character` value represents the gap between the
@@ -321,6 +490,832 @@ impl DesignRoot {
         FindAllReferences::search(self, decl_pos)
     }
 
+    /// Find all references like find_all_references but pass each reference to the callback
+    /// as soon as it is found, the search stops when the cancel flag is set
+    pub fn find_references_streaming(
+        &self,
+        decl_pos: &SrcPos,
+        on_reference: &mut dyn FnMut(&SrcPos),
+        cancel: &AtomicBool,
+    ) {
+        FindReferencesStreaming::search(self, decl_pos, on_reference, cancel)
+    }
+
+    /// Positions to edit when renaming a declaration, the declaration itself and
+    /// all references to it
+    /// Renaming an architecture also includes the block configurations `for <arch>`
+    /// of the configurations of its entity
+    pub fn rename(&self, decl_pos: &SrcPos) -> Vec<SrcPos> {
+        let mut positions = self.find_all_references(decl_pos);
+
+        if let Some((entity_pos, arch_name)) = self.find_architecture(decl_pos) {
+            // Architectures are not declarations found by the search
+            if !positions.contains(decl_pos) {
+                positions.insert(0, decl_pos.clone());
+            }
+
+            for library in self.libraries.values() {
+                for unit in library.units.values() {
+                    if let AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(ref config)) =
+                        *unit.unit.read()
+                    {
+                        let entity_ref = match config.entity_name.item {
+                            SelectedName::Designator(ref designator) => &designator.reference,
+                            SelectedName::Selected(_, ref designator) => &designator.item.reference,
+                        };
+
+                        if entity_ref.as_ref() != Some(&entity_pos) {
+                            continue;
+                        }
+
+                        let block_spec = &config.block_config.block_spec;
+                        if let Name::Designator(ref designator) = block_spec.item {
+                            if designator.item == Designator::Identifier(arch_name.clone()) {
+                                positions.push(block_spec.pos.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        positions
+    }
+
+    /// The positions to edit when renaming a declaration together with the declarations
+    /// already having the new name in the design units where the declaration is used
+    /// Declarations of design units and their regions as well as declarations of the region
+    /// where the renamed declaration is declared are found as conflicts, overloading a
+    /// subprogram with another subprogram is not considered a conflict
+    pub fn rename_preview(&self, decl_pos: &SrcPos, new_name: &str) -> RenamePreview {
+        let edits = self.rename(decl_pos);
+        let designator = Designator::Identifier(self.symbol_utf8(new_name));
+        let renamed_is_overloaded = self
+            .find_declared_entity(decl_pos)
+            .map_or(false, |ent| ent.is_overloaded());
+
+        let mut conflicts: Vec<RenameConflict> = Vec::new();
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                let index = IndexData::new(&*unit.unit.read());
+                let pos = index
+                    .declarations
+                    .iter()
+                    .find(|pos| *pos == decl_pos)
+                    .or_else(|| {
+                        index
+                            .references
+                            .iter()
+                            .find(|(_, reference)| reference == decl_pos)
+                            .map(|(pos, _)| pos)
+                    });
+
+                let pos = if let Some(pos) = pos {
+                    pos
+                } else {
+                    continue;
+                };
+
+                let analysis = self.get_analysis(unit);
+                let result = analysis.result();
+                let mut visible: Vec<NamedEntities> = result
+                    .region
+                    .lookup_within(pos, &designator)
+                    .ok()
+                    .into_iter()
+                    .collect();
+
+                // A local declaration conflicts with the declarations of its own region
+                for region in result.local_regions.iter() {
+                    if region
+                        .immediates()
+                        .any(|ent| ent.decl_pos() == Some(decl_pos))
+                    {
+                        visible.extend(region.lookup_immediate(&designator).cloned());
+                    }
+                }
+
+                for visible in visible.iter() {
+                    if renamed_is_overloaded && visible.as_non_overloaded().is_none() {
+                        continue;
+                    }
+
+                    let entities: Vec<_> = match visible {
+                        NamedEntities::Single(ref ent) => vec![ent],
+                        NamedEntities::Overloaded(ref overloaded) => {
+                            overloaded.entities().collect()
+                        }
+                    };
+
+                    for ent in entities {
+                        if let Some(existing) = ent.decl_pos() {
+                            let conflict = RenameConflict {
+                                pos: pos.clone(),
+                                decl_pos: existing.clone(),
+                            };
+                            if existing != decl_pos && !conflicts.contains(&conflict) {
+                                conflicts.push(conflict);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        conflicts.sort_by(|left, right| {
+            let key = |conflict: &RenameConflict| {
+                let (pos, decl_pos) = (&conflict.pos, &conflict.decl_pos);
+                (
+                    pos.file_name().to_owned(),
+                    pos.start(),
+                    decl_pos.file_name().to_owned(),
+                    decl_pos.start(),
+                )
+            };
+            key(left).cmp(&key(right))
+        });
+        RenamePreview { edits, conflicts }
+    }
+
+    /// The entity declaration and name of the architecture declared at the position
+    fn find_architecture(&self, decl_pos: &SrcPos) -> Option<(SrcPos, Symbol)> {
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                if unit.ident().pos != *decl_pos {
+                    continue;
+                }
+
+                if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch)) =
+                    *unit.unit.read()
+                {
+                    let entity_pos = arch.entity_name.reference.clone()?;
+                    return Some((entity_pos, arch.ident.item.clone()));
+                }
+            }
+        }
+        None
+    }
+
+    /// Comments of all sources containing any of the patterns, such as TODO or FIXME
+    /// The text of the comment excludes the comment delimiters
+    pub fn task_comments(&self, patterns: &[&str]) -> Vec<(SrcPos, String)> {
+        let mut sources: Vec<Source> = Vec::new();
+        for library in self.libraries.values() {
+            for source in library.sources.iter() {
+                if !sources.contains(source) {
+                    sources.push(source.clone());
+                }
+            }
+        }
+        sources.sort_by_key(|source| source.file_name().to_owned());
+
+        let mut result = Vec::new();
+        for source in sources.iter() {
+            for (pos, text) in source_comments(&self.symbols, source) {
+                if patterns.iter().any(|pattern| text.contains(pattern)) {
+                    result.push((pos, text.trim().to_owned()));
+                }
+            }
+        }
+        result
+    }
+
+    /// The kind of the declaration referenced at the cursor
+    pub fn reference_kind(&self, source: &Source, cursor: Position) -> Option<SymbolKind> {
+        let decl_pos = self.search_reference(source, cursor)?;
+        let ent = self.find_declared_entity(&decl_pos)?;
+        Some(ent.kind().symbol_kind())
+    }
+
+    /// The signature, type and documentation comment of the declaration referenced at the cursor
+    pub fn tooltip(&self, source: &Source, cursor: Position) -> Option<Tooltip> {
+        let decl_pos = self.search_reference(source, cursor)?;
+        let ent = self.find_declared_entity(&decl_pos)?;
+
+        let type_mark = if let Some(subtype) = ent.object_subtype() {
+            Some(subtype.base())
+        } else {
+            ent.signature()
+                .and_then(|signature| signature.return_type())
+        };
+
+        Some(Tooltip {
+            signature: ent.describe(),
+            type_info: type_mark.map(|type_mark| TypeInfo {
+                name: type_mark.designator().to_string(),
+                decl_pos: type_mark.decl_pos().cloned(),
+            }),
+            doc: self.doc_comment(&decl_pos),
+            decl_pos,
+        })
+    }
+
+    /// The design unit or declaration within a design unit declared at the position
+    fn find_declared_entity(&self, decl_pos: &SrcPos) -> Option<Arc<NamedEntity>> {
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                if unit.ident().pos.source != decl_pos.source {
+                    continue;
+                }
+
+                let analysis = self.get_analysis(unit);
+                let ent = analysis
+                    .result()
+                    .declarations()
+                    .find(|ent| ent.decl_pos() == Some(decl_pos));
+                if let Some(ent) = ent {
+                    return Some(ent.clone());
+                }
+            }
+        }
+        None
+    }
+
+    /// The text of the consecutive comment lines directly before the line of the declaration
+    fn doc_comment(&self, decl_pos: &SrcPos) -> Option<String> {
+        let comments = source_comments(&self.symbols, &decl_pos.source);
+        let contents = decl_pos.source.contents();
+
+        let mut lines = Vec::new();
+        let mut lineno = decl_pos.start().line as usize;
+        while lineno > 0 {
+            lineno -= 1;
+
+            let is_comment_line = contents
+                .get_line(lineno)
+                .map_or(false, |line| line.trim_start().starts_with("--"));
+            if !is_comment_line {
+                break;
+            }
+
+            if let Some((_, text)) = comments
+                .iter()
+                .find(|(pos, _)| pos.start().line as usize == lineno)
+            {
+                lines.push(text.trim().to_owned());
+            }
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            lines.reverse();
+            Some(lines.join("\n"))
+        }
+    }
+
+    /// The kind of the design units and the declarations within them
+    /// by file name and range of the declaration
+    fn declaration_kinds(&self) -> FnvHashMap<(PathBuf, crate::data::Range), SymbolKind> {
+        let mut kinds = FnvHashMap::default();
+
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                let analysis = self.get_analysis(unit);
+
+                for ent in analysis.result().declarations() {
+                    if let Some(decl_pos) = ent.decl_pos() {
+                        kinds
+                            .entry(decl_key(decl_pos))
+                            .or_insert_with(|| ent.kind().symbol_kind());
+                    }
+                }
+            }
+        }
+        kinds
+    }
+
+    /// All resolved references of the project together with the position and kind of the
+    /// referenced declaration, sorted by the position of the reference
+    pub fn cross_references(&self) -> Vec<(SrcPos, SrcPos, SymbolKind)> {
+        let kinds = self.declaration_kinds();
+        let mut references = Vec::new();
+
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                // A secondary unit without primary unit is not analyzed and may keep the
+                // references of a previous analysis
+                if let UnitKey::Secondary(ref primary_name, _) = unit.key() {
+                    if !library
+                        .units
+                        .contains_key(&UnitKey::Primary(primary_name.clone()))
+                    {
+                        continue;
+                    }
+                }
+
+                let index = IndexData::new(&*unit.unit.read());
+                for (pos, decl_pos) in index.references.into_iter() {
+                    if let Some(kind) = kinds.get(&decl_key(&decl_pos)) {
+                        references.push((pos, decl_pos, *kind));
+                    }
+                }
+            }
+        }
+
+        references.sort_by(|(left, ..), (right, ..)| {
+            (left.file_name(), left.start()).cmp(&(right.file_name(), right.start()))
+        });
+        references
+    }
+
+    /// The smallest expression containing the cursor together with its type
+    /// Returns None when there is no such expression or when its type cannot be determined
+    pub fn expression_at(&self, source: &Source, cursor: Position) -> Option<(SrcPos, TypeInfo)> {
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                if unit.ident().pos.source != *source {
+                    continue;
+                }
+
+                let expr = ExpressionAtCursor::search(&*unit.unit.read(), source, cursor);
+                if let Some(expr) = expr {
+                    // Local declarations such as those of a process or subprogram are not
+                    // kept after analysis, the expression is therefore typed while analyzing
+                    // a copy of the design unit in the region where the expression is found
+                    let mut design_unit = (*self.get_analysis(unit)).clone();
+                    let context =
+                        AnalyzeContext::new(self, unit.unit_id()).with_type_probe(&expr.pos);
+                    context
+                        .analyze_design_unit(
+                            super::named_entity::new_id(),
+                            &mut design_unit,
+                            &mut Region::default(),
+                            &mut Region::default(),
+                            &mut Vec::new(),
+                        )
+                        .ok()?;
+                    let typ = context.probed_type()?;
+                    return Some((expr.pos, typ.type_info()));
+                }
+            }
+        }
+        None
+    }
+
+    /// The value of the 'simple_name, 'path_name or 'instance_name attribute at the cursor
+    /// Returns None when the value is not known without elaboration
+    pub fn name_attribute_value(&self, source: &Source, cursor: Position) -> Option<String> {
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                if unit.ident().pos.source != *source {
+                    continue;
+                }
+
+                let expr = ExpressionAtCursor::search(&*unit.unit.read(), source, cursor);
+                if let Some(WithPos {
+                    item: Expression::Name(ref name),
+                    ..
+                }) = expr
+                {
+                    if let Name::Attribute(ref attr) = name.as_ref() {
+                        let context = AnalyzeContext::new(self, unit.unit_id());
+                        let analysis = self.get_analysis(unit);
+                        return context.static_name_attribute(&analysis.result().region, attr);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// List the members of a package declaration together with the number of references
+    /// from outside of the package, members without such references could be moved to the body
+    /// The references are the ones found by the latest analysis
+    pub fn package_api(&self, pkg: &UnitId) -> Vec<MemberUsage> {
+        let unit = if let Some(unit) = self
+            .get_library_units(pkg.library_name())
+            .and_then(|units| units.get(pkg.key()))
+        {
+            unit
+        } else {
+            return Vec::new();
+        };
+
+        let mut members: Vec<_> = self
+            .get_analysis(unit)
+            .result()
+            .region
+            .immediates()
+            .filter(|ent| ent.is_explicit())
+            .filter_map(|ent| {
+                ent.decl_pos().map(|decl_pos| MemberUsage {
+                    designator: ent.designator().clone(),
+                    decl_pos: decl_pos.clone(),
+                    external_references: 0,
+                })
+            })
+            .collect();
+
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                let unit_id = unit.unit_id();
+                if unit_id.library_name() == pkg.library_name()
+                    && unit_id.primary_name() == pkg.primary_name()
+                {
+                    // The package itself or its body
+                    continue;
+                }
+
+                let index = IndexData::new(&*unit.unit.read());
+                for (_, reference) in index.references.iter() {
+                    for member in members.iter_mut() {
+                        if &member.decl_pos == reference {
+                            member.external_references += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        members.sort_by_key(|member| member.decl_pos.range().start);
+        members
+    }
+
+    /// The library and use clauses which make the declarations of other design units
+    /// referenced by the unit visible, the library clauses are sorted before the use clauses
+    /// A package with referenced members gets a single use clause of all its members while
+    /// the implicit 'library std, work' and 'use std.standard.all' clauses are left out
+    /// The references of the context clause of the unit are not considered
+    /// The references are the ones found by the latest analysis
+    pub fn required_use_clauses(&self, unit_id: &UnitId) -> Vec<String> {
+        let unit = if let Some(unit) = self.get_unit(unit_id) {
+            unit
+        } else {
+            return Vec::new();
+        };
+
+        // The primary unit declaring each primary unit or package member and whether the
+        // declaration is a package member by file name and range of the declaration
+        let mut owners = FnvHashMap::default();
+        for library in self.libraries.values() {
+            for other in library.units.values() {
+                let other_id = other.unit_id();
+                let is_primary = match other_id.kind() {
+                    AnyKind::Primary(_) => true,
+                    AnyKind::Secondary(_) => false,
+                };
+                if !is_primary
+                    || (other_id.library_name() == unit_id.library_name()
+                        && other_id.primary_name() == unit_id.primary_name())
+                {
+                    // Not a primary unit or the unit itself or the primary unit of a
+                    // secondary unit which is always visible
+                    continue;
+                }
+
+                let analysis = self.get_analysis(other);
+                let result = analysis.result();
+                if let Some(decl_pos) = result.ent.as_ref().and_then(|ent| ent.decl_pos()) {
+                    owners.insert(decl_key(decl_pos), (other_id.clone(), false));
+                }
+
+                if other_id.kind() == AnyKind::Primary(PrimaryKind::Package) {
+                    for ent in result.region.immediates() {
+                        if let Some(decl_pos) = ent.decl_pos() {
+                            owners
+                                .entry(decl_key(decl_pos))
+                                .or_insert_with(|| (other_id.clone(), true));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut library_clauses = Vec::new();
+        let mut use_clauses = Vec::new();
+        let index = IndexData::new(&*unit.unit.read());
+        for (pos, decl_pos) in index.references.iter() {
+            if pos.start() < unit.ident().pos.start() {
+                // A reference of the context clause
+                continue;
+            }
+
+            let (owner, is_member) = if let Some(owner) = owners.get(&decl_key(decl_pos)) {
+                owner
+            } else {
+                continue;
+            };
+
+            let library_name = owner.library_name().name_utf8();
+            let primary_name = owner.primary_name().name_utf8();
+            if library_name == "std" && primary_name == "standard" {
+                continue;
+            }
+
+            let prefix = if owner.library_name() == unit_id.library_name() {
+                "work".to_owned()
+            } else {
+                if library_name != "std" {
+                    library_clauses.push(format!("library {};", library_name));
+                }
+                library_name
+            };
+
+            if *is_member {
+                use_clauses.push(format!("use {}.{}.all;", prefix, primary_name));
+            }
+        }
+
+        library_clauses.sort();
+        library_clauses.dedup();
+        use_clauses.sort();
+        use_clauses.dedup();
+        library_clauses.append(&mut use_clauses);
+        library_clauses
+    }
+
+    /// All enumeration types declared in the regions of design units of all libraries
+    /// sorted by the position of the type declaration
+    pub fn enum_types(&self) -> Vec<EnumInfo> {
+        let mut enum_types: Vec<EnumInfo> = Vec::new();
+
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                let analysis = self.get_analysis(unit);
+                for ent in analysis.result().region.immediates() {
+                    if let NamedEntityKind::TypeDeclaration(Type::Enum, ..) = ent.kind() {
+                        let decl_pos = if let Some(decl_pos) = ent.decl_pos() {
+                            decl_pos
+                        } else {
+                            continue;
+                        };
+
+                        let literals = ent
+                            .kind()
+                            .implicit_declarations()
+                            .iter()
+                            .filter(|implicit| {
+                                if let NamedEntityKind::EnumLiteral(..) = implicit.kind() {
+                                    true
+                                } else {
+                                    false
+                                }
+                            })
+                            .map(|literal| literal.designator().clone())
+                            .collect();
+
+                        enum_types.push(EnumInfo {
+                            designator: ent.designator().clone(),
+                            decl_pos: decl_pos.clone(),
+                            literals,
+                        });
+                    }
+                }
+            }
+        }
+
+        enum_types.sort_by(|left, right| {
+            let (left, right) = (&left.decl_pos, &right.decl_pos);
+            (left.file_name(), left.start()).cmp(&(right.file_name(), right.start()))
+        });
+        enum_types.dedup_by(|info, other| info.decl_pos == other.decl_pos);
+        enum_types
+    }
+
+    /// The entity implemented by an architecture
+    pub fn entity_of_architecture(&self, arch: &UnitId) -> Option<UnitId> {
+        if arch.kind() != AnyKind::Secondary(SecondaryKind::Architecture) {
+            return None;
+        }
+
+        let unit = self
+            .get_library_units(arch.library_name())?
+            .get(&UnitKey::Primary(arch.primary_name().clone()))?;
+
+        if unit.kind() == AnyKind::Primary(PrimaryKind::Entity) {
+            Some(unit.unit_id().clone())
+        } else {
+            None
+        }
+    }
+
+    /// The package body of a package declaration
+    pub fn package_body_of(&self, pkg: &UnitId) -> Option<UnitId> {
+        if pkg.kind() != AnyKind::Primary(PrimaryKind::Package) {
+            return None;
+        }
+
+        let unit = self
+            .get_library_units(pkg.library_name())?
+            .get(&UnitKey::Secondary(
+                pkg.primary_name().clone(),
+                pkg.primary_name().clone(),
+            ))?;
+
+        if unit.kind() == AnyKind::Secondary(SecondaryKind::PackageBody) {
+            Some(unit.unit_id().clone())
+        } else {
+            None
+        }
+    }
+
+    /// The package declaration of a package body
+    pub fn package_decl_of(&self, body: &UnitId) -> Option<UnitId> {
+        if body.kind() != AnyKind::Secondary(SecondaryKind::PackageBody) {
+            return None;
+        }
+
+        let unit = self
+            .get_library_units(body.library_name())?
+            .get(&UnitKey::Primary(body.primary_name().clone()))?;
+
+        if unit.kind() == AnyKind::Primary(PrimaryKind::Package) {
+            Some(unit.unit_id().clone())
+        } else {
+            None
+        }
+    }
+
+    /// All design units parsed from the source in the order they appear
+    /// A source added to several libraries contributes units to each of them
+    /// Units rejected as duplicates are included with the id of the unit they duplicate
+    pub fn units_in_source(&self, source: &Source) -> Vec<UnitId> {
+        let mut library_names: Vec<_> = self.libraries.keys().collect();
+        library_names.sort_by_key(|name| name.name_utf8());
+
+        let mut result = Vec::new();
+        for library_name in library_names {
+            let library = &self.libraries[library_name];
+            let mut units: Vec<&LockedUnit> = Vec::new();
+            if let Some(unit_ids) = library.units_by_source.get(source) {
+                units.extend(unit_ids.iter().map(|unit_id| &library.units[unit_id.key()]));
+            }
+            units.extend(
+                library
+                    .duplicates
+                    .iter()
+                    .map(|(_, unit)| unit)
+                    .filter(|unit| unit.source() == source),
+            );
+            units.sort_by_key(|unit| unit.ident().pos.start());
+            result.extend(units.into_iter().map(|unit| unit.unit_id().clone()));
+        }
+        result
+    }
+
+    /// All design units which depend on the unit directly or indirectly based on the
+    /// dependencies registered during the latest analysis
+    /// Units with a 'use library.all' clause of the library of the unit are included
+    pub fn dependents_of(&self, unit_id: &UnitId) -> FnvHashSet<UnitId> {
+        let mut affected = FnvHashSet::default();
+        affected.insert(unit_id.clone());
+        if let Some(library_all_users) =
+            self.users_of_library_all.read().get(unit_id.library_name())
+        {
+            affected.extend(library_all_users.iter().cloned());
+        }
+
+        let mut dependents = get_all_affected(&self.users_of.read(), affected);
+        dependents.remove(unit_id);
+        dependents
+    }
+
+    /// The files affected by an edit of the source
+    /// Files are parsed independently so only the edited file must be parsed again while
+    /// the files of design units depending on the units of the edited file must be analyzed again
+    pub fn edit_impact(&self, source: &Source) -> EditImpact {
+        let mut reanalyze: Vec<Source> = Vec::new();
+        for unit_id in self.units_in_source(source) {
+            for dependent in self.dependents_of(&unit_id) {
+                if let Some(unit) = self.get_unit(&dependent) {
+                    let dependent_source = &unit.ident().pos.source;
+                    if dependent_source != source && !reanalyze.contains(dependent_source) {
+                        reanalyze.push(dependent_source.clone());
+                    }
+                }
+            }
+        }
+        reanalyze.sort_by_key(|source| source.file_name().to_owned());
+
+        EditImpact {
+            reparse: vec![source.clone()],
+            reanalyze,
+        }
+    }
+
+    /// All architectures of an entity in the order they appear in the source files
+    pub fn architectures_of_entity(&self, entity: &UnitId) -> Vec<UnitId> {
+        if entity.kind() != AnyKind::Primary(PrimaryKind::Entity) {
+            return Vec::new();
+        }
+
+        if let Some(library) = self.libraries.get(entity.library_name()) {
+            library
+                .sorted_unit_ids()
+                .into_iter()
+                .filter(|unit_id| {
+                    unit_id.kind() == AnyKind::Secondary(SecondaryKind::Architecture)
+                        && unit_id.primary_name() == entity.primary_name()
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// All processes of all architectures with the clock and reset inferred from the
+    /// leading statement of each process
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        let clock_edge = ClockEdge::new(|name| self.symbol_utf8(name));
+        let mut library_names: Vec<_> = self.libraries.keys().collect();
+        library_names.sort_by_key(|name| name.name_utf8());
+
+        let mut processes = Vec::new();
+        for library_name in library_names {
+            let library = &self.libraries[library_name];
+            for unit_id in library.sorted_unit_ids() {
+                if unit_id.kind() != AnyKind::Secondary(SecondaryKind::Architecture) {
+                    continue;
+                }
+
+                if let Some(unit) = library.units.get(unit_id.key()) {
+                    if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch)) =
+                        *unit.unit.read()
+                    {
+                        clock_edge.collect_processes(&unit_id, &arch.statements, &mut processes);
+                    }
+                }
+            }
+        }
+        processes
+    }
+
+    /// All configuration declarations with the entity and architecture they configure and
+    /// the binding indications of their component configurations
+    pub fn configurations(&self) -> Vec<ConfigInfo> {
+        let mut library_names: Vec<_> = self.libraries.keys().collect();
+        library_names.sort_by_key(|name| name.name_utf8());
+
+        let mut configurations = Vec::new();
+        for library_name in library_names {
+            let library = &self.libraries[library_name];
+            for unit_id in library.sorted_unit_ids() {
+                if unit_id.kind() != AnyKind::Primary(PrimaryKind::Configuration) {
+                    continue;
+                }
+
+                let unit = if let Some(unit) = library.units.get(unit_id.key()) {
+                    unit
+                } else {
+                    continue;
+                };
+
+                if let AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(ref config)) =
+                    *self.get_analysis(unit)
+                {
+                    let reference = match config.entity_name.item {
+                        SelectedName::Designator(ref designator) => &designator.reference,
+                        SelectedName::Selected(_, ref designator) => &designator.item.reference,
+                    };
+                    let entity = reference
+                        .as_ref()
+                        .and_then(|decl_pos| self.entity_declared_at(decl_pos));
+
+                    let architecture = entity.as_ref().and_then(|entity| {
+                        let arch_name = block_spec_identifier(&config.block_config)?;
+                        let arch = self.get_library_units(entity.library_name())?.get(
+                            &UnitKey::Secondary(entity.primary_name().clone(), arch_name.clone()),
+                        )?;
+                        if arch.kind() == AnyKind::Secondary(SecondaryKind::Architecture) {
+                            Some(arch.unit_id().clone())
+                        } else {
+                            None
+                        }
+                    });
+
+                    let mut bindings = Vec::new();
+                    collect_bindings(&config.block_config, &mut bindings);
+
+                    configurations.push(ConfigInfo {
+                        configuration: unit_id.clone(),
+                        ident: config.ident.clone(),
+                        entity,
+                        architecture_name: config.block_config.block_spec.clone(),
+                        architecture,
+                        bindings,
+                    });
+                }
+            }
+        }
+        configurations
+    }
+
+    /// The entity declared at a position
+    fn entity_declared_at(&self, decl_pos: &SrcPos) -> Option<UnitId> {
+        self.libraries.values().find_map(|library| {
+            library.units.values().find_map(|unit| {
+                if unit.kind() == AnyKind::Primary(PrimaryKind::Entity)
+                    && &unit.ident().pos == decl_pos
+                {
+                    Some(unit.unit_id().clone())
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
     pub(super) fn symbol_utf8(&self, name: &str) -> Symbol {
         self.symbols.symtab().insert_utf8(name)
     }
@@ -348,6 +1343,13 @@ impl DesignRoot {
                     false
                 };
 
+                // The references of the design unit are complete once it is analyzed
+                let index = if self.index_during_analysis {
+                    Some(IndexData::new(&*unit))
+                } else {
+                    None
+                };
+
                 let root_region = Arc::new(root_region);
                 let region = Arc::new(region);
 
@@ -385,6 +1387,8 @@ impl DesignRoot {
                     region,
                     ent,
                     has_circular_dependency,
+                    local_regions: context.take_local_regions(),
+                    index,
                 };
 
                 unit.finish(result)
@@ -399,10 +1403,18 @@ impl DesignRoot {
             .and_then(|library| library.units.get(&unit_id.key()))
     }
 
-    fn reset_affected(&self, mut affected: FnvHashSet<UnitId>) {
+    fn reset_all(&self) {
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                unit.unit.reset();
+            }
+        }
+    }
+
+    fn reset_affected(&self, affected: &FnvHashSet<UnitId>) {
         // Reset analysis state of all design units
-        for unit_id in affected.drain() {
-            if let Some(unit) = self.get_unit(&unit_id) {
+        for unit_id in affected.iter() {
+            if let Some(unit) = self.get_unit(unit_id) {
                 unit.unit.reset();
             }
         }
@@ -479,7 +1491,8 @@ impl DesignRoot {
 
     /// Resets the analysis state of all design units which need to be re-analyzed
     /// because another design unit has been added or removed.
-    fn reset(&mut self) {
+    /// Returns the added or removed units and the other units which were reset
+    fn reset(&mut self) -> (FnvHashSet<UnitId>, FnvHashSet<UnitId>) {
         let mut removed = FnvHashSet::default();
         let mut added = FnvHashSet::default();
 
@@ -492,7 +1505,8 @@ impl DesignRoot {
             }
         }
 
-        let mut affected: FnvHashSet<_> = added.union(&removed).cloned().collect();
+        let invalidated: FnvHashSet<_> = added.union(&removed).cloned().collect();
+        let mut affected = invalidated.clone();
         let changed: FnvHashSet<_> = removed.intersection(&added).cloned().collect();
         removed = removed.difference(&changed).cloned().collect();
         added = added.difference(&changed).cloned().collect();
@@ -532,7 +1546,10 @@ impl DesignRoot {
             }
         }
 
-        self.reset_affected(get_all_affected(&users_of, affected));
+        let mut cleared = get_all_affected(&users_of, affected);
+        self.reset_affected(&cleared);
+        cleared
+            .retain(|unit_id| !invalidated.contains(unit_id) && self.get_unit(unit_id).is_some());
         drop(users_of);
         drop(users_of_library_all);
         drop(missing_primary);
@@ -555,13 +1572,43 @@ impl DesignRoot {
                 !unit_ids.is_empty()
             });
         }
+
+        (invalidated, cleared)
     }
 
     pub fn analyze(&mut self, diagnostics: &mut dyn DiagnosticHandler) {
-        self.reset();
+        self.analyze_with_events(diagnostics, &mut |_| {});
+    }
+
+    /// Analyze like analyze and report the changes of the analysis state as events
+    /// Invalidated and cleared units are reported before the units are analyzed again
+    pub fn analyze_with_events(
+        &mut self,
+        diagnostics: &mut dyn DiagnosticHandler,
+        events: &mut dyn FnMut(AnalysisEvent),
+    ) {
+        let (invalidated, cleared) = self.reset();
+        for unit_id in sorted_by_name(invalidated) {
+            events(AnalysisEvent::UnitInvalidated(unit_id));
+        }
+        for unit_id in sorted_by_name(cleared) {
+            events(AnalysisEvent::ReferencesCleared(unit_id));
+        }
 
+        let mut not_analyzed = Vec::new();
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                if !unit.unit.is_analyzed() {
+                    not_analyzed.push(unit.unit_id().clone());
+                }
+            }
+        }
+
+        // Libraries are stored in a hash map so diagnostics are collected and sorted
+        // before being emitted to get a stable order
+        let mut all_diagnostics = Vec::new();
         for library in self.libraries.values_mut() {
-            library.refresh(diagnostics);
+            library.refresh(&mut all_diagnostics);
         }
 
         use rayon::prelude::*;
@@ -578,14 +1625,95 @@ impl DesignRoot {
             self.get_analysis(*unit);
         });
 
-        // Emit diagnostics sorted within a file
         for library in self.libraries.values() {
             for unit_id in library.sorted_unit_ids() {
                 let unit = library.units.get(&unit_id.key()).unwrap();
-                diagnostics.append(unit.unit.expect_analyzed().result().diagnostics.clone());
+                all_diagnostics.extend(
+                    unit.unit
+                        .expect_analyzed()
+                        .result()
+                        .diagnostics
+                        .iter()
+                        .cloned(),
+                );
             }
         }
+
+        sort_diagnostics(&mut all_diagnostics);
+        diagnostics.append(all_diagnostics);
+
+        for unit_id in sorted_by_name(not_analyzed) {
+            events(AnalysisEvent::UnitAnalyzed(unit_id));
+        }
     }
+
+    /// Analyze after the changed sources were added again and return only the diagnostics
+    /// of the changed sources and of the sources of design units depending on them
+    /// Dependents are taken both from before and after the analysis so that a unit which
+    /// no longer depends on a changed unit still gets its diagnostics reported once
+    pub fn analyze_changed(&mut self, changed_sources: &[Source]) -> Vec<Diagnostic> {
+        let mut scope: Vec<Source> = changed_sources.to_vec();
+        self.add_affected_sources(changed_sources, &mut scope);
+
+        let mut diagnostics = Vec::new();
+        self.analyze(&mut diagnostics);
+
+        self.add_affected_sources(changed_sources, &mut scope);
+        diagnostics.retain(|diagnostic| scope.contains(&diagnostic.pos.source));
+        diagnostics
+    }
+
+    fn add_affected_sources(&self, changed_sources: &[Source], scope: &mut Vec<Source>) {
+        for source in changed_sources.iter() {
+            for affected in self.edit_impact(source).reanalyze {
+                if !scope.contains(&affected) {
+                    scope.push(affected);
+                }
+            }
+        }
+    }
+
+    /// Analyze and index the declarations and references of the analyzed design units
+    /// Each design unit is indexed when its analysis finishes and the index is kept with
+    /// the analysis, so unchanged design units are not searched again on later calls
+    /// All design units are re-analyzed on the first call
+    /// The diagnostics are the same as those of analyze
+    pub fn analyze_and_index(&mut self) -> (Vec<Diagnostic>, IndexData) {
+        if !self.index_during_analysis {
+            self.index_during_analysis = true;
+            self.reset_all();
+        }
+
+        let mut diagnostics = Vec::new();
+        self.analyze(&mut diagnostics);
+
+        let mut index = IndexData::default();
+        for library in self.libraries.values() {
+            for unit_id in library.sorted_unit_ids() {
+                let unit = &library.units[unit_id.key()];
+                if let Some(ref unit_index) = self.get_analysis(unit).result().index {
+                    index
+                        .declarations
+                        .extend(unit_index.declarations.iter().cloned());
+                    index
+                        .references
+                        .extend(unit_index.references.iter().cloned());
+                }
+            }
+        }
+        (diagnostics, index)
+    }
+}
+
+fn decl_key(decl_pos: &SrcPos) -> (PathBuf, crate::data::Range) {
+    (decl_pos.file_name().to_path_buf(), decl_pos.range())
+}
+
+/// Sort design units by library and name to report them in a stable order
+fn sorted_by_name(unit_ids: impl IntoIterator<Item = UnitId>) -> Vec<UnitId> {
+    let mut unit_ids: Vec<_> = unit_ids.into_iter().collect();
+    unit_ids.sort_by_key(|unit_id| (unit_id.library_name().name_utf8(), unit_id.describe()));
+    unit_ids
 }
 
 fn get_all_affected(
@@ -5,6 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::analyze::*;
+use super::lint::Lint;
 use super::region::*;
 use crate::ast::Range;
 use crate::ast::*;
@@ -169,6 +170,7 @@ impl<'a> AnalyzeContext<'a> {
             }
             Name::Indexed(ref mut prefix, ref mut exprs) => {
                 self.resolve_name(region, &prefix.pos, &mut prefix.item, diagnostics)?;
+                self.check_array_prefix(region, prefix, "index", diagnostics);
                 for expr in exprs.iter_mut() {
                     self.analyze_expression(region, expr, diagnostics)?;
                 }
@@ -177,6 +179,7 @@ impl<'a> AnalyzeContext<'a> {
 
             Name::Slice(ref mut prefix, ref mut drange) => {
                 self.resolve_name(region, &prefix.pos, &mut prefix.item, diagnostics)?;
+                self.check_array_prefix(region, prefix, "slice", diagnostics);
                 self.analyze_discrete_range(region, drange.as_mut(), diagnostics)?;
                 Ok(Some(ResolvedName::Unknown))
             }
@@ -254,11 +257,14 @@ impl<'a> AnalyzeContext<'a> {
         let AttributeName {
             name,
             signature,
+            attr,
             expr,
-            ..
         } = attr;
 
-        self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
+        let resolved = self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
+        if signature.is_none() {
+            self.check_attribute_prefix(&name.pos, resolved.as_ref(), attr, diagnostics);
+        }
 
         if let Some(ref mut signature) = signature {
             if let Err(err) = self.resolve_signature(region, signature) {
@@ -281,6 +287,7 @@ impl<'a> AnalyzeContext<'a> {
             Range::Range(ref mut constraint) => {
                 self.analyze_expression(region, &mut constraint.left_expr, diagnostics)?;
                 self.analyze_expression(region, &mut constraint.right_expr, diagnostics)?;
+                self.check_range_bound_types(region, constraint, diagnostics);
             }
             Range::Attribute(ref mut attr) => {
                 self.analyze_attribute_name(region, attr, diagnostics)?
@@ -306,11 +313,40 @@ impl<'a> AnalyzeContext<'a> {
             }
             DiscreteRange::Range(ref mut range) => {
                 self.analyze_range(region, range, diagnostics)?;
+                if let Range::Range(ref constraint) = range {
+                    self.check_discrete_range_bounds(region, constraint, diagnostics);
+                }
             }
         }
         Ok(())
     }
 
+    /// Analyze the discrete range of a loop or generate parameter and return the subtype of
+    /// the parameter when the range is given by a type mark
+    pub fn analyze_parameter_range(
+        &self,
+        region: &Region<'_>,
+        drange: &mut DiscreteRange,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<Subtype>> {
+        if let DiscreteRange::Discrete(ref mut type_mark, ref mut range) = drange {
+            let subtype = match self.resolve_type_mark(region, type_mark) {
+                Ok(type_mark) => Some(Subtype::new(type_mark)),
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                    None
+                }
+            };
+            if let Some(ref mut range) = range {
+                self.analyze_range(region, range, diagnostics)?;
+            }
+            Ok(subtype)
+        } else {
+            self.analyze_discrete_range(region, drange, diagnostics)?;
+            Ok(None)
+        }
+    }
+
     pub fn analyze_choices(
         &self,
         region: &Region<'_>,
@@ -361,13 +397,40 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    pub fn analyze_delay_mechanism(
+        &self,
+        region: &Region<'_>,
+        delay_mechanism: &mut Option<DelayMechanism>,
+        rhs: &AssignmentRightHand<Waveform>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalNullResult {
+        if let Some(DelayMechanism::Inertial {
+            reject: Some(ref mut reject),
+        }) = delay_mechanism
+        {
+            self.analyze_expression(region, reject, diagnostics)?;
+            self.check_reject(region, reject, rhs, diagnostics);
+        }
+        Ok(())
+    }
+
     pub fn analyze_assoc_elems(
         &self,
         region: &Region<'_>,
         elems: &mut Vec<AssociationElement>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
-        for AssociationElement { actual, .. } in elems.iter_mut() {
+        let mut named = false;
+        for AssociationElement { formal, actual } in elems.iter_mut() {
+            if formal.is_some() {
+                named = true;
+            } else if named {
+                diagnostics.push(Diagnostic::error(
+                    &actual.pos,
+                    "Positional association not allowed after named association",
+                ));
+            }
+
             match actual.item {
                 ActualPart::Expression(ref mut expr) => {
                     self.analyze_expression_pos(region, &actual.pos, expr, diagnostics)?;
@@ -383,10 +446,61 @@ impl<'a> AnalyzeContext<'a> {
         region: &Region<'_>,
         fcall: &mut FunctionCall,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> FatalNullResult {
+    ) -> FatalResult<Option<ResolvedName>> {
         let FunctionCall { name, parameters } = fcall;
-        self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
-        self.analyze_assoc_elems(region, parameters, diagnostics)
+        let resolved = self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
+        // An object prefix of a function call is an indexed name
+        self.check_array_prefix(region, name, "index", diagnostics);
+        self.analyze_assoc_elems(region, parameters, diagnostics)?;
+
+        // A call such as lib.pkg.fun(arg) or fun(arg) is resolved among the visible
+        // subprograms using the argument types when they are known
+        if let Some(ResolvedName::Known(NamedEntities::Overloaded(ref overloaded))) = resolved {
+            if let Some(candidates) = self.matching_subprograms(region, overloaded, parameters) {
+                if let [ent] = candidates.as_slice() {
+                    match name.item {
+                        Name::Selected(_, ref mut suffix) => suffix.set_unique_reference(ent),
+                        Name::Designator(ref mut designator) => {
+                            designator.set_unique_reference(ent)
+                        }
+                        _ => {}
+                    }
+                    return Ok(Some(ResolvedName::Known(NamedEntities::new(ent.clone()))));
+                } else if candidates.len() > 1 {
+                    check_ambiguous_string_literals(&candidates, parameters, diagnostics);
+                } else if let Name::Selected(_, ref mut suffix) = name.item {
+                    // The arguments of a call by a selected name may index the result of a
+                    // parameterless function, otherwise there must be a matching subprogram
+                    let indexable: Vec<_> = overloaded
+                        .entities()
+                        .filter(|ent| returns_array_without_arguments(ent))
+                        .collect();
+                    if let [ent] = indexable.as_slice() {
+                        suffix.set_unique_reference(ent);
+                    } else if indexable.is_empty() {
+                        let mut error = Diagnostic::error(
+                            &suffix.pos,
+                            format!(
+                                "No declaration of '{}' for {}",
+                                suffix.item.item,
+                                self.describe_argument_types(region, parameters)
+                            ),
+                        );
+                        let mut decl_positions: Vec<_> = overloaded
+                            .entities()
+                            .filter_map(|ent| ent.decl_pos())
+                            .collect();
+                        decl_positions
+                            .sort_by_key(|pos| (pos.source.file_name().to_owned(), pos.start()));
+                        for pos in decl_positions {
+                            error.add_related(pos, "Defined here");
+                        }
+                        diagnostics.push(error);
+                    }
+                }
+            }
+        }
+        Ok(resolved)
     }
 
     fn analyze_aggregate(
@@ -426,11 +540,143 @@ impl<'a> AnalyzeContext<'a> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         let QualifiedExpression { name, expr } = qexpr;
-        self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
+        let resolved = self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
         self.analyze_expression(region, expr, diagnostics)?;
+        if let Some(ResolvedName::Known(NamedEntities::Single(ref ent))) = resolved {
+            if ent.actual_kind().is_type() {
+                self.analyze_record_aggregate(ent, expr, diagnostics);
+            }
+        }
         Ok(())
     }
 
+    /// Resolve the choices of an aggregate of a record type to the fields of the record
+    /// and check that each field is associated exactly once
+    pub fn analyze_record_aggregate(
+        &self,
+        type_mark: &NamedEntity,
+        expr: &mut WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let fields = if let Some(Type::Record(ref fields)) = type_mark.base_type_class() {
+            fields
+        } else {
+            return;
+        };
+
+        let assocs = if let Expression::Aggregate(ref mut assocs) = expr.item {
+            assocs
+        } else {
+            return;
+        };
+
+        // A single positional element might as well be a parenthesized expression
+        if let [ElementAssociation::Positional(..)] = assocs.as_slice() {
+            return;
+        }
+
+        // Positional associations are only allowed before named associations
+        let positional = assocs
+            .iter()
+            .take_while(|assoc| {
+                if let ElementAssociation::Positional(..) = assoc {
+                    true
+                } else {
+                    false
+                }
+            })
+            .count();
+        let mut associated: Vec<(&Arc<NamedEntity>, Option<SrcPos>)> = fields
+            .iter()
+            .take(positional)
+            .map(|field| (field, None))
+            .collect();
+        let mut has_others = false;
+
+        for assoc in assocs.iter_mut() {
+            let choices = if let ElementAssociation::Named(ref mut choices, _) = assoc {
+                choices
+            } else {
+                continue;
+            };
+
+            for choice in choices.iter_mut() {
+                let choice_expr = match choice {
+                    Choice::Expression(ref mut choice_expr) => choice_expr,
+                    Choice::Others => {
+                        has_others = true;
+                        continue;
+                    }
+                    Choice::DiscreteRange(..) => continue,
+                };
+
+                let designator = if let Expression::Name(ref mut name) = choice_expr.item {
+                    if let Name::Designator(ref mut designator) = name.as_mut() {
+                        designator
+                    } else {
+                        continue;
+                    }
+                } else {
+                    continue;
+                };
+
+                let field = fields
+                    .iter()
+                    .find(|field| field.designator() == designator.designator());
+
+                let field = if let Some(field) = field {
+                    field
+                } else {
+                    diagnostics.push(Diagnostic::error(
+                        &choice_expr.pos,
+                        format!(
+                            "No declaration of '{}' within {}",
+                            designator.designator(),
+                            type_mark.base_type().describe()
+                        ),
+                    ));
+                    continue;
+                };
+
+                designator.set_unique_reference(field);
+
+                if let Some((_, prev_pos)) =
+                    associated.iter().find(|(prev, _)| prev.id() == field.id())
+                {
+                    let mut error = Diagnostic::error(
+                        &choice_expr.pos,
+                        format!(
+                            "Record field '{}' is already associated",
+                            field.designator()
+                        ),
+                    );
+                    if let Some(prev_pos) = prev_pos {
+                        error.add_related(prev_pos, "Previously chosen here");
+                    }
+                    diagnostics.push(error);
+                } else {
+                    associated.push((field, Some(choice_expr.pos.clone())));
+                }
+            }
+        }
+
+        if has_others {
+            return;
+        }
+
+        for field in fields.iter() {
+            if !associated.iter().any(|(prev, _)| prev.id() == field.id()) {
+                diagnostics.push(Diagnostic::error(
+                    &expr.pos,
+                    format!(
+                        "Missing association of record field '{}'",
+                        field.designator()
+                    ),
+                ));
+            }
+        }
+    }
+
     fn analyze_expression_pos(
         &self,
         region: &Region<'_>,
@@ -438,13 +684,30 @@ impl<'a> AnalyzeContext<'a> {
         expr: &mut Expression,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
-        match expr {
-            Expression::Binary(_, ref mut left, ref mut right) => {
+        let result = match expr {
+            Expression::Binary(op, ref mut left, ref mut right) => {
                 self.analyze_expression(region, left, diagnostics)?;
-                self.analyze_expression(region, right, diagnostics)
+                self.analyze_expression(region, right, diagnostics)?;
+                match op {
+                    Binary::QueEQ
+                    | Binary::QueNE
+                    | Binary::QueLT
+                    | Binary::QueLTE
+                    | Binary::QueGT
+                    | Binary::QueGTE
+                    | Binary::Concat => {
+                        self.check_operator_operands(region, pos, expr, diagnostics);
+                    }
+                    _ => {}
+                }
+                Ok(())
             }
-            Expression::Unary(_, ref mut inner) => {
-                self.analyze_expression(region, inner, diagnostics)
+            Expression::Unary(op, ref mut inner) => {
+                self.analyze_expression(region, inner, diagnostics)?;
+                if let Unary::QueQue = op {
+                    self.check_operator_operands(region, pos, expr, diagnostics);
+                }
+                Ok(())
             }
             Expression::Name(ref mut name) => {
                 self.resolve_name(region, pos, name, diagnostics)?;
@@ -465,8 +728,33 @@ impl<'a> AnalyzeContext<'a> {
                     self.analyze_subtype_indication(region, subtype, diagnostics)
                 }
             },
+            Expression::Conditional(ref mut conditionals) => {
+                let Conditionals {
+                    conditionals,
+                    else_item,
+                } = conditionals.as_mut();
+                for conditional in conditionals {
+                    let Conditional { condition, item } = conditional;
+                    self.analyze_expression(region, item, diagnostics)?;
+                    self.analyze_expression(region, condition, diagnostics)?;
+                    self.check_condition(region, condition, diagnostics);
+                }
+                if let Some(expr) = else_item {
+                    self.analyze_expression(region, expr, diagnostics)?;
+                } else {
+                    // Conditional expressions are only parsed where a value is required
+                    diagnostics.push(Diagnostic::error(
+                        pos,
+                        "Conditional expression must end with 'else' when used as a value",
+                    ));
+                }
+                Ok(())
+            }
             Expression::Literal(_) => Ok(()),
-        }
+        };
+
+        self.probe_expression_type(region, pos, expr);
+        result
     }
 
     // @TODO maybe make generic function for expression/waveform.
@@ -512,6 +800,10 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
         }
+
+        if self.is_lint_enabled(Lint::DirectionMismatch) {
+            self.lint_expr_assignment_direction(region, target, rhs, diagnostics);
+        }
         Ok(())
     }
 
@@ -556,6 +848,10 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
         }
+
+        if self.is_lint_enabled(Lint::DirectionMismatch) {
+            self.lint_waveform_assignment_direction(region, target, rhs, diagnostics);
+        }
         Ok(())
     }
 
@@ -567,7 +863,20 @@ impl<'a> AnalyzeContext<'a> {
     ) -> FatalNullResult {
         match target.item {
             Target::Name(ref mut name) => {
-                self.resolve_name(parent, &target.pos, name, diagnostics)?;
+                let resolved = self.resolve_name(parent, &target.pos, name, diagnostics)?;
+                if let Name::Designator(..) = name {
+                    if let Some(ResolvedName::Known(NamedEntities::Single(ref ent))) = resolved {
+                        if is_constant(ent.actual_kind()) {
+                            diagnostics.push(Diagnostic::error(
+                                &target.pos,
+                                capitalize(&format!(
+                                    "{} may not be the target of an assignment",
+                                    ent.describe()
+                                )),
+                            ));
+                        }
+                    }
+                }
             }
             Target::Aggregate(ref mut assocs) => {
                 self.analyze_aggregate(parent, assocs, diagnostics)?;
@@ -577,6 +886,71 @@ impl<'a> AnalyzeContext<'a> {
     }
 }
 
+/// True for a function which can be called without arguments and returns an array
+fn returns_array_without_arguments(ent: &NamedEntity) -> bool {
+    ent.signature().map_or(false, |signature| {
+        signature.can_be_called_with_arity(0)
+            && match signature
+                .return_type()
+                .and_then(|typ| typ.base_type_class())
+            {
+                Some(Type::Array(..)) => true,
+                _ => false,
+            }
+    })
+}
+
+/// Report string literal arguments whose type differs between the subprograms
+/// which can be called with the arguments
+fn check_ambiguous_string_literals(
+    candidates: &[Arc<NamedEntity>],
+    args: &[AssociationElement],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    for (idx, arg) in args.iter().enumerate() {
+        match arg.actual.item {
+            ActualPart::Expression(Expression::Literal(Literal::String(..))) => {}
+            _ => continue,
+        }
+
+        let mut types: Vec<&NamedEntity> = Vec::new();
+        for ent in candidates.iter() {
+            if let Some(signature) = ent.signature() {
+                if let Some(typ) = signature.param_types().get(idx) {
+                    if !types.iter().any(|other| other.id() == typ.id()) {
+                        types.push(typ);
+                    }
+                }
+            }
+        }
+
+        if types.len() > 1 {
+            let mut described: Vec<_> = types
+                .iter()
+                .map(|typ| format!("'{}'", typ.designator()))
+                .collect();
+            described.sort();
+            diagnostics.push(Diagnostic::error(
+                &arg.actual.pos,
+                format!(
+                    "Ambiguous string literal, it may be of type {}",
+                    described.join(" or ")
+                ),
+            ));
+        }
+    }
+}
+
+fn is_constant(kind: &NamedEntityKind) -> bool {
+    match kind {
+        NamedEntityKind::Object(ObjectClass::Constant, ..) | NamedEntityKind::DeferredConstant => {
+            true
+        }
+        NamedEntityKind::InterfaceObject(ref object) => object.class == ObjectClass::Constant,
+        _ => false,
+    }
+}
+
 // @TODO make method
 pub fn uninstantiated_package_prefix_error(
     named_entity: &NamedEntity,
@@ -590,3 +964,13 @@ pub fn uninstantiated_package_prefix_error(
         )),
     )
 }
+
+pub fn missing_library_clause_error(prefix: &SrcPos, library_name: &Symbol) -> Diagnostic {
+    Diagnostic::error(
+        prefix,
+        format!(
+            "Library '{}' is not visible, missing library clause 'library {};'",
+            library_name, library_name
+        ),
+    )
+}
@@ -11,6 +11,7 @@ use super::*;
 use crate::ast::*;
 use crate::data::*;
 use analyze::*;
+use declarative::{static_bounds, static_integer};
 use region::*;
 
 impl<'a> AnalyzeContext<'a> {
@@ -38,13 +39,19 @@ impl<'a> AnalyzeContext<'a> {
                     timeout_clause,
                 } = wait_stmt;
                 for name in sensitivity_clause.iter_mut() {
-                    self.resolve_name(parent, &name.pos, &mut name.item, diagnostics)?;
+                    let resolved =
+                        self.resolve_name(parent, &name.pos, &mut name.item, diagnostics)?;
+                    self.check_signal_name(&name.pos, resolved.as_ref(), diagnostics);
                 }
                 if let Some(expr) = condition_clause {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    self.check_condition(parent, expr, diagnostics);
                 }
                 if let Some(expr) = timeout_clause {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    if let Some(time) = self.standard_type("TIME") {
+                        self.check_expression_type(parent, expr, &time, diagnostics);
+                    }
                 }
             }
             SequentialStatement::Assert(ref mut assert_stmt) => {
@@ -56,16 +63,20 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_expression(parent, condition, diagnostics)?;
                 if let Some(expr) = report {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    self.check_report(parent, expr, diagnostics);
                 }
                 if let Some(expr) = severity {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    self.check_severity(parent, expr, diagnostics);
                 }
             }
             SequentialStatement::Report(ref mut report_stmt) => {
                 let ReportStatement { report, severity } = report_stmt;
                 self.analyze_expression(parent, report, diagnostics)?;
+                self.check_report(parent, report, diagnostics);
                 if let Some(expr) = severity {
                     self.analyze_expression(parent, expr, diagnostics)?;
+                    self.check_severity(parent, expr, diagnostics);
                 }
             }
             SequentialStatement::Exit(ref mut exit_stmt) => {
@@ -118,6 +129,7 @@ impl<'a> AnalyzeContext<'a> {
                     self.analyze_choices(parent, choices, diagnostics)?;
                     self.analyze_sequential_part(parent, item, diagnostics)?;
                 }
+                check_unreachable_alternatives(alternatives, diagnostics);
             }
             SequentialStatement::Loop(ref mut loop_stmt) => {
                 let LoopStatement {
@@ -126,15 +138,16 @@ impl<'a> AnalyzeContext<'a> {
                 } = loop_stmt;
                 match iteration_scheme {
                     Some(IterationScheme::For(ref mut index, ref mut drange)) => {
-                        self.analyze_discrete_range(parent, drange, diagnostics)?;
+                        let subtype = self.analyze_parameter_range(parent, drange, diagnostics)?;
                         let mut region = parent.nested();
                         let designator: WithPos<Designator> = index.clone().into();
                         region.add(
                             designator,
-                            NamedEntityKind::Object(ObjectClass::Constant),
+                            NamedEntityKind::Object(ObjectClass::Constant, subtype, None),
                             diagnostics,
                         );
                         self.analyze_sequential_part(&mut region, statements, diagnostics)?;
+                        self.add_local_region(&region);
                     }
                     Some(IterationScheme::While(ref mut expr)) => {
                         self.analyze_expression(parent, expr, diagnostics)?;
@@ -149,28 +162,52 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_function_call(parent, pcall, diagnostics)?;
             }
             SequentialStatement::SignalAssignment(ref mut assign) => {
-                // @TODO more
-                let SignalAssignment { target, rhs, .. } = assign;
+                let SignalAssignment {
+                    target,
+                    delay_mechanism,
+                    rhs,
+                } = assign;
                 self.analyze_waveform_assignment(parent, target, rhs, diagnostics)?;
+                self.analyze_delay_mechanism(parent, delay_mechanism, rhs, diagnostics)?;
+                self.check_string_literal_target(parent, target, rhs, diagnostics);
             }
             SequentialStatement::VariableAssignment(ref mut assign) => {
                 let VariableAssignment { target, rhs } = assign;
                 self.analyze_expr_assignment(parent, target, rhs, diagnostics)?;
+                self.check_string_literal_target(parent, target, rhs, diagnostics);
             }
             SequentialStatement::SignalForceAssignment(ref mut assign) => {
                 let SignalForceAssignment {
                     target,
-                    force_mode: _,
+                    force_mode,
                     rhs,
                 } = assign;
                 self.analyze_expr_assignment(parent, target, rhs, diagnostics)?;
+                self.check_force_mode(parent, target, force_mode, diagnostics);
+
+                match rhs {
+                    AssignmentRightHand::Simple(expr) => {
+                        self.check_target_type(parent, target, expr, diagnostics);
+                    }
+                    AssignmentRightHand::Conditional(conditionals) => {
+                        for conditional in conditionals.conditionals.iter() {
+                            self.check_target_type(parent, target, &conditional.item, diagnostics);
+                        }
+                        if let Some(ref expr) = conditionals.else_item {
+                            self.check_target_type(parent, target, expr, diagnostics);
+                        }
+                    }
+                    AssignmentRightHand::Selected(selection) => {
+                        for alternative in selection.alternatives.iter() {
+                            self.check_target_type(parent, target, &alternative.item, diagnostics);
+                        }
+                    }
+                }
             }
             SequentialStatement::SignalReleaseAssignment(ref mut assign) => {
-                let SignalReleaseAssignment {
-                    target,
-                    force_mode: _,
-                } = assign;
+                let SignalReleaseAssignment { target, force_mode } = assign;
                 self.analyze_target(parent, target, diagnostics)?;
+                self.check_force_mode(parent, target, force_mode, diagnostics);
             }
             SequentialStatement::Null => {}
         }
@@ -189,4 +226,220 @@ impl<'a> AnalyzeContext<'a> {
 
         Ok(())
     }
+
+    /// LRM 10.5.2.1 It is an error if a force mode of out is specified and the target is a port of mode in
+    fn check_force_mode(
+        &self,
+        region: &Region<'_>,
+        target: &WithPos<Target>,
+        force_mode: &Option<ForceMode>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if *force_mode != Some(ForceMode::Out) {
+            return;
+        }
+
+        let name = if let Target::Name(ref name) = target.item {
+            name
+        } else {
+            return;
+        };
+
+        if let Some(NamedEntities::Single(ent)) = self.lookup_name(region, &target.pos, name) {
+            if let NamedEntityKind::InterfaceObject(ref object) = ent.actual_kind() {
+                if object.mode == Mode::In {
+                    diagnostics.error(
+                        &target.pos,
+                        format!(
+                            "Force mode 'out' is not allowed for {} of mode 'in'",
+                            ent.describe()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A choice of a case alternative whose value is known statically
+enum StaticChoice {
+    Range(i64, i64),
+    Literal(Designator),
+    String(Latin1String),
+}
+
+impl StaticChoice {
+    fn from_choice(choice: &Choice) -> Option<(StaticChoice, SrcPos)> {
+        match choice {
+            Choice::Expression(ref expr) => {
+                let value = match expr.item {
+                    Expression::Literal(Literal::Character(chr)) => {
+                        StaticChoice::Literal(Designator::Character(chr))
+                    }
+                    Expression::Literal(Literal::String(ref string)) => {
+                        StaticChoice::String(string.clone())
+                    }
+                    Expression::Name(ref name) => {
+                        if let Name::Designator(ref designator) = name.as_ref() {
+                            StaticChoice::Literal(designator.item.clone())
+                        } else {
+                            return None;
+                        }
+                    }
+                    ref expr => {
+                        let value = static_integer(expr)?;
+                        StaticChoice::Range(value, value)
+                    }
+                };
+                Some((value, expr.pos.clone()))
+            }
+            Choice::DiscreteRange(DiscreteRange::Range(crate::ast::Range::Range(ref range))) => {
+                let (low, high) = static_bounds(range)?;
+                let pos = range.left_expr.pos.combine(&range.right_expr.pos);
+                Some((StaticChoice::Range(low, high), pos))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn choice_pos(choice: &Choice) -> Option<SrcPos> {
+    match choice {
+        Choice::Expression(ref expr) => Some(expr.pos.clone()),
+        Choice::DiscreteRange(DiscreteRange::Discrete(ref type_mark, _)) => {
+            Some(type_mark.pos.clone())
+        }
+        Choice::DiscreteRange(DiscreteRange::Range(crate::ast::Range::Range(ref range))) => {
+            Some(range.left_expr.pos.combine(&range.right_expr.pos))
+        }
+        Choice::DiscreteRange(DiscreteRange::Range(crate::ast::Range::Attribute(ref attr))) => {
+            Some(attr.name.pos.clone())
+        }
+        Choice::Others => None,
+    }
+}
+
+fn overlaps(left: &StaticChoice, right: &StaticChoice) -> bool {
+    match (left, right) {
+        (StaticChoice::Range(left_low, left_high), StaticChoice::Range(right_low, right_high)) => {
+            left_low <= right_high && right_low <= left_high
+        }
+        (StaticChoice::Literal(ref left), StaticChoice::Literal(ref right)) => left == right,
+        (StaticChoice::String(ref left), StaticChoice::String(ref right)) => left == right,
+        _ => false,
+    }
+}
+
+/// True if the choice is covered by the union of the previous choices
+fn is_covered(choice: &StaticChoice, previous: &[(StaticChoice, SrcPos)]) -> bool {
+    match choice {
+        StaticChoice::Range(low, high) => {
+            if low > high {
+                // Null range
+                return false;
+            }
+            let mut ranges: Vec<(i64, i64)> = previous
+                .iter()
+                .filter_map(|(prev, _)| match prev {
+                    StaticChoice::Range(prev_low, prev_high) => Some((*prev_low, *prev_high)),
+                    _ => None,
+                })
+                .collect();
+            ranges.sort_unstable();
+
+            let mut next = *low;
+            for (prev_low, prev_high) in ranges {
+                if prev_low > next {
+                    break;
+                }
+                if prev_high >= next {
+                    if prev_high >= *high {
+                        return true;
+                    }
+                    next = if let Some(next) = prev_high.checked_add(1) {
+                        next
+                    } else {
+                        // Covered up to the largest value
+                        return true;
+                    };
+                }
+            }
+            false
+        }
+        StaticChoice::Literal(ref designator) => previous.iter().any(|(prev, _)| match prev {
+            StaticChoice::Literal(ref prev) => prev == designator,
+            _ => false,
+        }),
+        StaticChoice::String(ref string) => previous.iter().any(|(prev, _)| match prev {
+            StaticChoice::String(ref prev) => prev == string,
+            _ => false,
+        }),
+    }
+}
+
+/// Check that every case alternative can be reached given the choices of the alternatives
+/// before it
+pub(super) fn check_unreachable_alternatives<T>(
+    alternatives: &[Alternative<T>],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut has_others = false;
+    let mut previous: Vec<(StaticChoice, SrcPos)> = Vec::new();
+
+    for alternative in alternatives.iter() {
+        let mut first_pos = None;
+        let mut is_reachable = false;
+        let mut choices = Vec::with_capacity(alternative.choices.len());
+
+        for choice in alternative.choices.iter() {
+            if let Choice::Others = choice {
+                is_reachable = true;
+                continue;
+            }
+
+            if let Some((value, pos)) = StaticChoice::from_choice(choice) {
+                if first_pos.is_none() {
+                    first_pos = Some(pos.clone());
+                }
+                if !is_covered(&value, &previous) {
+                    is_reachable = true;
+                }
+                choices.push((value, pos));
+            } else {
+                is_reachable = true;
+                if first_pos.is_none() {
+                    first_pos = choice_pos(choice);
+                }
+            }
+        }
+
+        if let Some(pos) = first_pos {
+            if has_others {
+                diagnostics.push(Diagnostic::error(
+                    pos,
+                    "Case alternative is unreachable after the 'others' choice",
+                ));
+            } else if !is_reachable {
+                let mut error = Diagnostic::error(
+                    pos,
+                    "Case alternative is unreachable, its choices are covered by previous choices",
+                );
+                for (prev, prev_pos) in previous.iter() {
+                    if choices.iter().any(|(value, _)| overlaps(value, prev)) {
+                        error.add_related(prev_pos, "Previously chosen here");
+                    }
+                }
+                diagnostics.push(error);
+            }
+        }
+
+        if alternative
+            .choices
+            .iter()
+            .any(|choice| *choice == Choice::Others)
+        {
+            has_others = true;
+        }
+        previous.extend(choices);
+    }
 }
@@ -30,6 +30,32 @@ end entity;
     )
 }
 
+#[test]
+fn missing_library_is_only_reported_at_library_clause() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+library missing_lib;
+use missing_lib.pkg.all;
+use missing_lib.all;
+
+entity ent is
+end entity;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("missing_lib"),
+            "No such library 'missing_lib'",
+        )],
+    )
+}
+
 #[test]
 fn library_clause_extends_into_secondary_units() {
     let mut builder = LibraryBuilder::new();
@@ -318,11 +344,66 @@ end entity;
         diagnostics,
         vec![Diagnostic::error(
             code.s("libname", 1),
-            "No declaration of 'libname'",
+            "Library 'libname' is not visible, missing library clause 'library libname;'",
         )],
     )
 }
 
+#[test]
+fn error_on_use_clause_of_library_which_is_not_visible() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("ieee"),
+            "Library 'ieee' is not visible, missing library clause 'library ieee;'",
+        )],
+    );
+}
+
+#[test]
+fn use_clause_of_library_with_library_clause() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+end package;
+",
+    );
+    builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
 #[test]
 fn nested_use_clause_missing() {
     let mut builder = LibraryBuilder::new();
@@ -962,6 +1043,84 @@ end package;
     );
 }
 
+#[test]
+fn context_reference_combined_with_explicit_library_clause() {
+    let mut builder = LibraryBuilder::new();
+    let ieee = builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  subtype std_logic is std_ulogic;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+context ctx is
+  library ieee;
+  use ieee.std_logic_1164.all;
+end context;
+
+library ieee;
+context work.ctx;
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+  port (clk : in std_logic);
+end entity;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let std_logic = root
+        .search_reference(
+            code.source(),
+            code.s1("clk : in std_logic").s1("std_logic").start(),
+        )
+        .unwrap();
+    assert_eq!(
+        std_logic,
+        ieee.s1("subtype std_logic").s1("std_logic").pos()
+    );
+}
+
+#[test]
+fn context_reference_makes_library_visible_for_following_use_clause() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  subtype std_logic is std_ulogic;
+end package;
+",
+    );
+    builder.code(
+        "libname",
+        "
+context ctx is
+  library ieee;
+end context;
+
+context work.ctx;
+use ieee.std_logic_1164.std_logic;
+
+entity ent is
+  port (clk : in std_logic);
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
 #[test]
 fn resolves_context_reference() {
     check_search_reference(
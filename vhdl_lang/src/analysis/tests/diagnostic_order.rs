@@ -0,0 +1,95 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::DesignRoot;
+use crate::data::Symbol;
+use crate::syntax::Symbols;
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+
+fn analyze_in_order(symbols: &Arc<Symbols>, files: &[(Symbol, Code)]) -> Vec<Diagnostic> {
+    let mut root = DesignRoot::new(symbols.clone());
+    add_standard_library(symbols.clone(), &mut root);
+    for (library_name, code) in files.iter() {
+        root.add_design_file(library_name.clone(), code.design_file());
+    }
+    let mut diagnostics = Vec::new();
+    root.analyze(&mut diagnostics);
+    diagnostics
+}
+
+#[test]
+fn diagnostics_are_sorted_by_position() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c0 : missing1 := 0;
+  constant c1 : missing2 := 0;
+begin
+end architecture;
+
+package pkg is
+  constant c2 : missing3 := 0;
+end package;
+",
+    );
+
+    let (mut root, diagnostics) = builder.get_analyzed_root();
+    assert_eq!(
+        diagnostics,
+        vec![
+            missing(&code, "missing1", 1),
+            missing(&code, "missing2", 1),
+            missing(&code, "missing3", 1),
+        ]
+    );
+
+    let mut reanalyzed = Vec::new();
+    root.analyze(&mut reanalyzed);
+    assert_eq!(diagnostics, reanalyzed);
+}
+
+#[test]
+fn diagnostics_order_does_not_depend_on_file_order() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg1 is
+  constant c0 : missing1 := 0;
+end package;",
+    );
+    builder.code(
+        "libname",
+        "
+package pkg2 is
+  constant c0 : missing2 := 0;
+end package;",
+    );
+    builder.code(
+        "otherlib",
+        "
+package pkg3 is
+  constant c0 : missing3 := 0;
+  constant c1 : missing4 := 0;
+end package;",
+    );
+
+    let symbols = builder.symbols();
+    let forward = builder.take_code();
+    let mut reverse = forward.clone();
+    reverse.reverse();
+
+    let diagnostics = analyze_in_order(&symbols, &forward);
+    assert_eq!(diagnostics.len(), 4);
+    assert_eq!(diagnostics, analyze_in_order(&symbols, &reverse));
+}
@@ -913,6 +913,10 @@ package body pkg is
     return false;
   end;
 
+  function name1 return natural is
+  begin
+    return 0;
+  end;
 end package body;
 ",
     );
@@ -990,3 +994,44 @@ end architecture;
         .related(code.s("homo1", 1), "Previously defined here")],
     );
 }
+
+#[test]
+fn error_on_declared_subprogram_without_body_in_package_body() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function fun(arg : natural) return natural;
+  procedure proc;
+  procedure missing(arg : natural);
+end package;
+
+package body pkg is
+  -- A helper only declared in the body
+  function helper(arg : natural) return natural is
+  begin
+    return arg + 1;
+  end;
+
+  function fun(arg : natural) return natural is
+  begin
+    return helper(arg);
+  end;
+
+  procedure proc is
+  begin
+  end;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("missing"),
+            "Missing body for procedure 'missing' with signature [INTEGER]",
+        )],
+    );
+}
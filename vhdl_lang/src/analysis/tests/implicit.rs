@@ -58,3 +58,88 @@ end package;
 ",
     );
 }
+
+#[test]
+fn adds_comparison_operators_for_enum_types() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  alias my_lt is \"<\"[enum_t, enum_t return boolean];
+  alias my_eq is \"=\"[enum_t, enum_t return boolean];
+  constant lt : boolean := \"<\"(alpha, beta);
+  constant lt2 : boolean := alpha < beta;
+end package;
+",
+    );
+}
+
+#[test]
+fn adds_concatenation_operators_for_array_types() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  type arr_t is array (natural range <>) of integer;
+  alias my_concat is \"&\"[arr_t, arr_t return arr_t];
+  alias my_concat_elem is \"&\"[arr_t, integer return arr_t];
+  alias my_concat_elem2 is \"&\"[integer, arr_t return arr_t];
+  constant c0 : arr_t := (0, 1);
+  constant c1 : arr_t := c0 & c0;
+  constant c2 : arr_t := \"&\"(c0, c1);
+end package;
+",
+    );
+}
+
+#[test]
+fn adds_implicit_operators_for_standard_types() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  alias time_eq is \"=\"[time, time return boolean];
+  alias bool_eq is \"=\"[boolean, boolean return boolean];
+  alias int_lt is \"<\"[integer, integer return boolean];
+  alias str_concat is \"&\"[string, string return string];
+  alias str_concat_char is \"&\"[string, character return string];
+  constant eq : boolean := \"=\"(1 ns, 2 ns);
+  constant str : string := \"&\"(\"ab\", \"cd\");
+end package;
+",
+    );
+}
+
+#[test]
+fn implicit_operators_are_visible_with_type() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+end package;
+
+use work.pkg.enum_t;
+
+package pkg2 is
+  alias my_lt is \"<\"[enum_t, enum_t return boolean];
+end package;
+",
+    );
+}
+
+#[test]
+fn explicit_operator_replaces_implicit_operator() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  function \"<\"(l, r : enum_t) return boolean;
+end package;
+
+package body pkg is
+  function \"<\"(l, r : enum_t) return boolean is
+  begin
+    return false;
+  end function;
+end package body;
+",
+    );
+}
@@ -34,6 +34,36 @@ end package;
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn allows_access_to_incomplete_type_within_its_full_definition() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type node_t;
+  type node_ptr_t is access node_t;
+  type node_t is record
+    value : natural;
+    next_node : node_ptr_t;
+  end record;
+
+  procedure append(variable list : inout node_ptr_t; value : natural);
+end package;
+
+package body pkg is
+  procedure append(variable list : inout node_ptr_t; value : natural) is
+  begin
+    list := new node_t'(value => value, next_node => list);
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
 #[test]
 fn error_on_duplicate_incomplete_type_definition() {
     let mut builder = LibraryBuilder::new();
@@ -5,9 +5,9 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
-use crate::analysis::DesignRoot;
+use crate::analysis::{AnalysisEvent, DesignRoot, SymbolKind};
 use crate::ast::search::*;
-use crate::ast::Reference;
+use crate::ast::{Reference, UnitId};
 use crate::data::SrcPos;
 use fnv::FnvHashSet;
 
@@ -256,6 +256,157 @@ end package body;
     check_incremental_analysis(builder, vec![]);
 }
 
+#[test]
+fn analysis_events_of_removed_source() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant const : natural := 0;
+end package;
+",
+    );
+
+    builder.code(
+        "libname",
+        "
+use work.pkg;
+
+package pkg2 is
+end package;
+",
+    );
+
+    let (mut root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let lib = code.symbol("libname");
+    let pkg = UnitId::package(&lib, &code.symbol("pkg"));
+    let pkg2 = UnitId::package(&lib, &code.symbol("pkg2"));
+
+    root.remove_source(lib, code.source());
+    let mut events = Vec::new();
+    let mut diagnostics = Vec::new();
+    root.analyze_with_events(&mut diagnostics, &mut |event| events.push(event));
+
+    assert_eq!(
+        events,
+        vec![
+            AnalysisEvent::UnitInvalidated(pkg),
+            AnalysisEvent::ReferencesCleared(pkg2.clone()),
+            AnalysisEvent::UnitAnalyzed(pkg2),
+        ]
+    );
+
+    // Nothing has changed since the previous analysis
+    let mut events = Vec::new();
+    root.analyze_with_events(&mut Vec::new(), &mut |event| events.push(event));
+    assert_eq!(events, Vec::new());
+}
+
+#[test]
+fn cross_references_of_use_within_package() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  constant const : natural := 0;
+end package;
+",
+    );
+
+    let pkg2 = builder.code(
+        "libname",
+        "
+use work.pkg.const;
+
+package pkg2 is
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let references = root.cross_references();
+    assert!(references.contains(&(
+        pkg2.s1("const").pos(),
+        pkg.s1("const :").s1("const").pos(),
+        SymbolKind::Constant
+    )));
+    assert!(references.contains(&(
+        pkg2.s1("pkg").pos(),
+        pkg.s1("pkg").pos(),
+        SymbolKind::Package
+    )));
+}
+
+#[test]
+fn analyze_changed_reports_diagnostics_of_changed_source_and_dependents() {
+    let mut builder = LibraryBuilder::new();
+    let pkg = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : natural := 0;
+end package;
+",
+    );
+    let user = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+package user is
+  constant d : natural := c;
+end package;
+",
+    );
+    let unrelated = builder.code(
+        "libname",
+        "
+package unrelated is
+  constant e : missing_t := 0;
+end package;
+",
+    );
+
+    let (mut root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            unrelated.s1("missing_t"),
+            "No declaration of 'missing_t'",
+        )],
+    );
+
+    let lib = pkg.symbol("libname");
+    let edited = CodeBuilder {
+        symbols: pkg.symbols.clone(),
+    }
+    .code_with_file_name(
+        pkg.source().file_name(),
+        "
+package pkg is
+  constant b : missing2_t := 0;
+end package;
+",
+    );
+    root.remove_source(lib.clone(), pkg.source());
+    root.add_design_file(lib, edited.design_file());
+
+    let diagnostics = root.analyze_changed(&[edited.source().clone()]);
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(edited.s1("missing2_t"), "No declaration of 'missing2_t'"),
+            Diagnostic::error(user.s1(":= c").s1("c"), "No declaration of 'c'"),
+        ],
+    );
+}
+
 fn check_incremental_analysis(builder: LibraryBuilder, expected_diagnostics: Vec<Diagnostic>) {
     let symbols = builder.symbols();
     let codes = builder.take_code();
@@ -317,6 +468,9 @@ fn check_analysis_equal(got: &mut DesignRoot, expected: &mut DesignRoot) -> Vec<
     let diff: FnvHashSet<_> = got_refs.symmetric_difference(&expected_refs).collect();
     assert_eq!(diff, FnvHashSet::default());
 
+    // Check that the cross references do not depend on the order of analysis
+    assert_eq!(got.cross_references(), expected.cross_references());
+
     got_diagnostics
 }
 
@@ -0,0 +1,623 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{EnumInfo, MemberUsage, ResetInfo, SymbolKind};
+use crate::ast::search::IndexData;
+use crate::ast::{Designator, EntityAspect, PrimaryKind, SecondaryKind, UnitId};
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn analyze_and_index_has_same_diagnostics_as_analyze() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant decl : natural := 0;
+  constant c0 : natural := decl;
+  constant c1 : missing := decl;
+end package;
+",
+    );
+
+    let mut root = builder.get_unanalyzed_root();
+    let (diagnostics, index) = root.analyze_and_index();
+    assert_eq!(diagnostics, vec![missing(&code, "missing", 1)]);
+    assert_eq!(diagnostics, builder.analyze());
+
+    let decl_pos = code.s1("decl").pos();
+    assert!(index
+        .references
+        .contains(&(code.s("decl", 2).pos(), decl_pos.clone())));
+    assert!(index
+        .references
+        .contains(&(code.s("decl", 3).pos(), decl_pos.clone())));
+    assert_eq!(
+        index.find_all_references(&decl_pos),
+        root.find_all_references(&decl_pos)
+    );
+
+    let searched = IndexData::new(&root);
+    assert_eq!(index.declarations, searched.declarations);
+    assert_eq!(index.references, searched.references);
+}
+
+#[test]
+fn package_api_counts_external_references() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant used : natural := 0;
+  constant unused : natural := used;
+end package;
+
+package body pkg is
+  constant local : natural := unused + used;
+end package body;
+
+use work.pkg.all;
+
+package other is
+  constant c0 : natural := used;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let pkg = UnitId::package(&code.symbol("libname"), &code.symbol("pkg"));
+    assert_eq!(
+        root.package_api(&pkg),
+        vec![
+            MemberUsage {
+                designator: Designator::Identifier(code.symbol("used")),
+                decl_pos: code.s1("used").pos(),
+                external_references: 1,
+            },
+            MemberUsage {
+                designator: Designator::Identifier(code.symbol("unused")),
+                decl_pos: code.s1("unused").pos(),
+                external_references: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn processes_infer_clock_and_asynchronous_reset() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk, rst_n, d : in bit; q : out bit);
+end entity;
+
+architecture rtl of ent is
+begin
+  seq : process (clk, rst_n)
+  begin
+    if rst_n = '0' then
+      q <= '0';
+    elsif clk'event and clk = '1' then
+      q <= d;
+    end if;
+  end process;
+
+  process (d)
+  begin
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let processes = root.processes();
+    assert_eq!(processes.len(), 2);
+
+    let rtl = UnitId::secondary(
+        &code.symbol("libname"),
+        SecondaryKind::Architecture,
+        &code.symbol("ent"),
+        &code.symbol("rtl"),
+    );
+    assert_eq!(processes[0].architecture, rtl);
+    assert_eq!(
+        processes[0].label.as_ref().map(|label| &label.item),
+        Some(&code.symbol("seq"))
+    );
+    assert_eq!(processes[0].clock, Some(code.symbol("clk")));
+    assert_eq!(
+        processes[0].reset,
+        Some(ResetInfo {
+            signal: code.symbol("rst_n"),
+            active_high: false,
+            asynchronous: true,
+        })
+    );
+
+    assert_eq!(processes[1].label, None);
+    assert_eq!(processes[1].clock, None);
+    assert_eq!(processes[1].reset, None);
+}
+
+#[test]
+fn processes_infer_clock_of_selected_edge_function() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  function rising_edge(signal s : bit) return boolean;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+
+entity ent is
+  port (clk, d : in bit; q : out bit);
+end entity;
+
+architecture rtl of ent is
+begin
+  process (clk)
+  begin
+    if ieee.std_logic_1164.rising_edge(clk) then
+      q <= d;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let processes = root.processes();
+    assert_eq!(processes.len(), 1);
+    assert_eq!(processes[0].clock, Some(code.symbol("clk")));
+    assert_eq!(processes[0].reset, None);
+}
+
+#[test]
+fn reference_kind_of_constant_and_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant width : natural := 8;
+end package;
+
+entity child is
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+use work.pkg.width;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit_vector(width - 1 downto 0);
+begin
+  inst : entity work.child;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.reference_kind(code.source(), code.s("width", 3).start()),
+        Some(SymbolKind::Constant)
+    );
+    assert_eq!(
+        root.reference_kind(code.source(), code.s("child", 3).start()),
+        Some(SymbolKind::Entity)
+    );
+    assert_eq!(
+        root.reference_kind(code.source(), code.s1("downto").start()),
+        None
+    );
+}
+
+#[test]
+fn reference_kind_and_tooltip_of_local_declarations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : natural;
+  end record;
+
+  function incr(value : natural) return natural is
+  begin
+    return value + 1;
+  end function;
+begin
+  main : process
+    -- The number of iterations
+    variable count : natural := 0;
+    variable rec : rec_t := (field => 0);
+  begin
+    count := incr(count);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let count_ref = code.s1("incr(count)").s1("count");
+    assert_eq!(
+        root.reference_kind(code.source(), count_ref.start()),
+        Some(SymbolKind::Variable)
+    );
+    assert_eq!(
+        root.reference_kind(code.source(), code.s1("value + 1").start()),
+        Some(SymbolKind::Constant)
+    );
+    assert_eq!(
+        root.reference_kind(code.source(), code.s1("field =>").s1("field").start()),
+        Some(SymbolKind::RecordField)
+    );
+
+    let tooltip = root.tooltip(code.source(), count_ref.start()).unwrap();
+    assert_eq!(tooltip.decl_pos, code.s1("count").pos());
+    assert_eq!(tooltip.signature, "variable 'count'");
+    assert_eq!(
+        tooltip.type_info.map(|type_info| type_info.name),
+        Some("NATURAL".to_owned())
+    );
+    assert_eq!(tooltip.doc, Some("The number of iterations".to_owned()));
+
+    let references = root.cross_references();
+    assert!(references.contains(&(
+        count_ref.pos(),
+        code.s1("count").pos(),
+        SymbolKind::Variable
+    )));
+    assert!(references.contains(&(
+        code.s1("value + 1").s1("value").pos(),
+        code.s1("value").pos(),
+        SymbolKind::Constant
+    )));
+    assert!(references.contains(&(
+        code.s1("field =>").s1("field").pos(),
+        code.s1("field").pos(),
+        SymbolKind::RecordField
+    )));
+}
+
+#[test]
+fn tooltip_of_documented_constant() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  -- The number of bits
+  -- of a data word
+  constant width : natural := 8;
+end package;
+
+use work.pkg.width;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit_vector(width - 1 downto 0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let tooltip = root
+        .tooltip(code.source(), code.s("width", 3).start())
+        .unwrap();
+    assert_eq!(tooltip.decl_pos, code.s1("width").pos());
+    assert_eq!(tooltip.signature, "constant 'width'");
+    assert_eq!(
+        tooltip.type_info.map(|type_info| type_info.name),
+        Some("NATURAL".to_owned())
+    );
+    assert_eq!(
+        tooltip.doc,
+        Some("The number of bits\nof a data word".to_owned())
+    );
+
+    // Declarations without comments have no documentation
+    let tooltip = root
+        .tooltip(code.source(), code.s("pkg", 2).start())
+        .unwrap();
+    assert_eq!(tooltip.signature, "package 'pkg'");
+    assert_eq!(tooltip.type_info, None);
+    assert_eq!(tooltip.doc, None);
+}
+
+#[test]
+fn task_comments() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+-- TODO: fix this
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit; -- FIXME later
+  -- Just a comment
+begin
+end architecture;
+-- TODO the end",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    // The standard library sources have comments of their own
+    let task_comments = |patterns: &[&str]| {
+        root.task_comments(patterns)
+            .into_iter()
+            .filter(|(pos, _)| &pos.source == code.source())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        task_comments(&["TODO", "FIXME"]),
+        vec![
+            (
+                code.s1("-- TODO: fix this").pos(),
+                "TODO: fix this".to_owned()
+            ),
+            (code.s1("-- FIXME later").pos(), "FIXME later".to_owned()),
+            (code.s1("-- TODO the end").pos(), "TODO the end".to_owned()),
+        ]
+    );
+    assert_eq!(
+        task_comments(&["FIXME"]),
+        vec![(code.s1("-- FIXME later").pos(), "FIXME later".to_owned())]
+    );
+}
+
+#[test]
+fn enum_types_with_literals_in_declaration_order() {
+    let mut builder = LibraryBuilder::new();
+    let ieee = builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy, done);
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let enum_types = root.enum_types();
+    let find = |decl_pos: crate::data::SrcPos| {
+        enum_types
+            .iter()
+            .find(|info| info.decl_pos == decl_pos)
+            .cloned()
+    };
+
+    let chars = |literals: &str| {
+        literals
+            .bytes()
+            .map(Designator::Character)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        find(ieee.s1("std_ulogic").pos()),
+        Some(EnumInfo {
+            designator: Designator::Identifier(ieee.symbol("std_ulogic")),
+            decl_pos: ieee.s1("std_ulogic").pos(),
+            literals: chars("UX01ZWLH-"),
+        })
+    );
+    assert_eq!(
+        find(code.s1("state_t").pos()),
+        Some(EnumInfo {
+            designator: Designator::Identifier(code.symbol("state_t")),
+            decl_pos: code.s1("state_t").pos(),
+            literals: vec![
+                Designator::Identifier(code.symbol("idle")),
+                Designator::Identifier(code.symbol("busy")),
+                Designator::Identifier(code.symbol("done")),
+            ],
+        })
+    );
+
+    // The standard package is included
+    assert!(enum_types
+        .iter()
+        .any(|info| info.designator == Designator::Identifier(code.symbol("boolean"))));
+}
+
+#[test]
+fn configurations_with_entity_architecture_and_bindings() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+end entity;
+
+architecture beh of child is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture rtl of ent is
+  component child is
+  end component;
+begin
+  inst : child;
+end architecture;
+
+configuration cfg of ent is
+  for rtl
+    for inst : child
+      use entity work.child(beh);
+    end for;
+  end for;
+end configuration;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let configurations = root.configurations();
+    assert_eq!(configurations.len(), 1);
+    let cfg = &configurations[0];
+
+    assert_eq!(
+        cfg.configuration,
+        UnitId::primary(
+            &code.symbol("libname"),
+            PrimaryKind::Configuration,
+            &code.symbol("cfg")
+        )
+    );
+    assert_eq!(cfg.ident.pos, code.s1("cfg").pos());
+    assert_eq!(
+        cfg.entity,
+        Some(UnitId::primary(
+            &code.symbol("libname"),
+            PrimaryKind::Entity,
+            &code.symbol("ent")
+        ))
+    );
+    assert_eq!(
+        cfg.architecture_name.pos,
+        code.s1("for rtl").s1("rtl").pos()
+    );
+    assert_eq!(
+        cfg.architecture,
+        Some(UnitId::secondary(
+            &code.symbol("libname"),
+            SecondaryKind::Architecture,
+            &code.symbol("ent"),
+            &code.symbol("rtl"),
+        ))
+    );
+
+    assert_eq!(cfg.bindings.len(), 1);
+    assert_eq!(
+        cfg.bindings[0].component_name.pos,
+        code.s1("for inst : child").s1("child").pos()
+    );
+    assert_matches!(
+        cfg.bindings[0].entity_aspect,
+        Some(EntityAspect::Entity(ref name, Some(ref arch))) => {
+            assert_eq!(name.pos, code.s1("work.child").pos());
+            assert_eq!(arch.pos, code.s("beh", 2).pos());
+        }
+    );
+}
+
+#[test]
+fn required_use_clauses_of_referenced_packages() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant const : natural := 0;
+end package;
+
+package unrelated is
+  constant unused : natural := 0;
+end package;
+
+library otherlib;
+use work.unrelated.all;
+use otherlib.opkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c0 : natural := work.pkg.const;
+  constant c1 : natural := ocon + c0;
+begin
+end architecture;
+",
+    );
+    builder.code(
+        "otherlib",
+        "
+package opkg is
+  constant ocon : natural := 0;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let arch = UnitId::secondary(
+        &code.symbol("libname"),
+        SecondaryKind::Architecture,
+        &code.symbol("ent"),
+        &code.symbol("a"),
+    );
+    assert_eq!(
+        root.required_use_clauses(&arch),
+        vec![
+            "library otherlib;".to_owned(),
+            "use otherlib.opkg.all;".to_owned(),
+            "use work.pkg.all;".to_owned(),
+        ]
+    );
+
+    let ent = UnitId::primary(
+        &code.symbol("libname"),
+        PrimaryKind::Entity,
+        &code.symbol("ent"),
+    );
+    assert_eq!(root.required_use_clauses(&ent), Vec::<String>::new());
+}
@@ -0,0 +1,811 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::analysis::{Casing, CasingConvention, DeclarationKind, Lint};
+
+#[test]
+fn variable_copied_after_assignment_in_clocked_process() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::VariableAsSignal);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk : in bit; d : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  process (clk)
+    variable v1, v2 : bit;
+  begin
+    if clk'event and clk = '1' then
+      v1 := d;
+      v2 := v1;
+      q <= v2;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::hint(
+            code.s("v1;", 1).s1("v1"),
+            "Variable is read after being assigned in the same clock edge, the new value is used and not the previous one",
+        )],
+    );
+}
+
+#[test]
+fn variable_copied_before_assignment_in_clocked_process() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::VariableAsSignal);
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk : in bit; d : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  process (clk)
+    variable v1, v2 : bit;
+  begin
+    if clk'event and clk = '1' then
+      q <= v2;
+      v2 := v1;
+      v1 := d;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn variable_as_signal_is_not_checked_by_default() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk : in bit; d : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  process (clk)
+    variable v1, v2 : bit;
+  begin
+    if clk'event and clk = '1' then
+      v1 := d;
+      v2 := v1;
+      q <= v2;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn function_calling_itself_unconditionally() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::UnconditionalRecursion);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    return n * fact(n - 1);
+  end function;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("fact(n - 1)"),
+            "'fact' calls itself unconditionally, the recursion never terminates",
+        )],
+    );
+}
+
+#[test]
+fn function_declared_in_package_calling_itself_unconditionally() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::UnconditionalRecursion);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function fact(n : natural) return natural;
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    return n * fact(n - 1);
+  end function;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("fact(n - 1)"),
+            "'fact' calls itself unconditionally, the recursion never terminates",
+        )],
+    );
+}
+
+#[test]
+fn function_calling_overload_or_short_circuit_is_not_recursion() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::UnconditionalRecursion);
+    builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function f(x : integer) return integer is
+  begin
+    return x;
+  end function;
+
+  function f(x : bit) return integer is
+  begin
+    return f(0);
+  end function;
+
+  function g(n : natural) return boolean is
+  begin
+    return n = 0 or g(n - 1);
+  end function;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn function_calling_itself_after_base_case() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::UnconditionalRecursion);
+    builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    if n = 0 then
+      return 1;
+    end if;
+    return n * fact(n - 1);
+  end function;
+
+  procedure proc(n : natural) is
+  begin
+    if n > 0 then
+      proc(n - 1);
+    end if;
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn identifiers_not_following_casing_convention() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Constant,
+        Casing::UpperSnakeCase.into(),
+    ));
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Type,
+        Casing::PascalCase.into(),
+    ));
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant maxCount : natural := 10;
+  constant MIN_COUNT : natural := 0;
+  type state_t is (idle, busy);
+  type WordArray is array (natural range <>) of bit;
+  signal someSignal : bit;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("maxCount"),
+                "Constant 'maxCount' does not follow UPPER_SNAKE_CASE, consider renaming to 'MAX_COUNT'",
+            ),
+            Diagnostic::warning(
+                code.s1("state_t"),
+                "Type 'state_t' does not follow PascalCase, consider renaming to 'StateT'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn identifiers_not_following_casing_pattern() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Constant,
+        CasingConvention::from_pattern("C_{UPPER_SNAKE_CASE}").unwrap(),
+    ));
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Type,
+        CasingConvention::from_pattern("{lower_snake_case}_t").unwrap(),
+    ));
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c_max_count : natural := 10;
+  constant C_MIN_COUNT : natural := 0;
+  constant WIDTH : natural := 0;
+  type state_t is (idle, busy);
+  type WordArray is array (natural range <>) of bit;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("c_max_count"),
+                "Constant 'c_max_count' does not follow C_{UPPER_SNAKE_CASE}, consider renaming to 'C_MAX_COUNT'",
+            ),
+            Diagnostic::warning(
+                code.s1("WIDTH"),
+                "Constant 'WIDTH' does not follow C_{UPPER_SNAKE_CASE}, consider renaming to 'C_WIDTH'",
+            ),
+            Diagnostic::warning(
+                code.s1("WordArray"),
+                "Type 'WordArray' does not follow {lower_snake_case}_t, consider renaming to 'word_array_t'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn casing_convention_of_declaration_kind_overrides_object_convention() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Object,
+        Casing::LowerSnakeCase.into(),
+    ));
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Constant,
+        Casing::UpperSnakeCase.into(),
+    ));
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant MAX_COUNT : natural := 10;
+  constant min_count : natural := 0;
+  signal some_signal : bit;
+  signal otherSignal : bit;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("min_count"),
+                "Constant 'min_count' does not follow UPPER_SNAKE_CASE, consider renaming to 'MIN_COUNT'",
+            ),
+            Diagnostic::warning(
+                code.s1("otherSignal"),
+                "Signal 'otherSignal' does not follow lower_snake_case, consider renaming to 'other_signal'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn no_casing_convention_for_bundled_libraries() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::Casing(
+        DeclarationKind::Constant,
+        Casing::UpperSnakeCase.into(),
+    ));
+    builder.code(
+        "ieee",
+        "
+package math_real is
+  constant math_pi : real := 3.14159;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn declaration_shadowing_standard_name() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::ShadowsStandardName);
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  subtype std_logic is std_ulogic;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type std_logic is ('0', '1');
+  constant natural : integer := 0;
+  type my_logic is ('0', '1');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::warning(
+                code.s1("std_logic"),
+                "'std_logic' shadows a declaration of ieee.std_logic_1164",
+            ),
+            Diagnostic::warning(
+                code.s1("natural"),
+                "'natural' shadows a declaration of std.standard",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn signal_assigned_literal_once_could_be_constant() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::SignalCouldBeConstant);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (o : out bit);
+end entity;
+
+architecture a of ent is
+  signal zero : bit;
+  signal toggled : bit;
+  signal connected : bit;
+
+  component comp is
+    port (o : out bit);
+  end component;
+begin
+  zero <= '0';
+  o <= zero;
+
+  process
+  begin
+    toggled <= '0';
+    wait for 10 ns;
+    toggled <= '1';
+    wait;
+  end process;
+
+  connected <= '0';
+  inst : comp port map (o => connected);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::hint(
+            code.s1("zero"),
+            "Signal 'zero' is only assigned a constant value, consider declaring it as a constant",
+        )],
+    );
+}
+
+#[test]
+fn signal_assigned_element_wise_is_not_constant() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::SignalCouldBeConstant);
+    builder.code(
+        "libname",
+        "
+package pkg is
+  function resolved (s : bit_vector) return bit;
+  subtype resolved_bit_vector is (resolved) bit_vector;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal parts : resolved_bit_vector(1 downto 0);
+  signal connected : bit_vector(1 downto 0);
+
+  component comp is
+    port (o : out bit);
+  end component;
+begin
+  parts <= \"00\";
+
+  process
+  begin
+    parts(0) <= '1';
+    wait;
+  end process;
+
+  connected <= \"00\";
+  inst : comp port map (o => connected(1));
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn signal_could_be_constant_is_not_enabled_by_default() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal zero : bit;
+begin
+  zero <= '0';
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn assignment_of_opposite_range_direction() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::DirectionMismatch);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (d : in bit_vector(7 downto 0));
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal src : bit_vector(0 to 7);
+  signal dst : bit_vector(7 downto 0);
+begin
+  dst <= src;
+  inst : entity work.ent port map (d => src);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::info(
+                code.s("src", 2),
+                "Range direction 'to' does not match direction 'downto' of 'dst'",
+            ),
+            Diagnostic::info(
+                code.s("src", 3),
+                "Range direction 'to' does not match direction 'downto' of port 'd'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn assignment_of_same_range_direction() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::DirectionMismatch);
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal src : bit_vector(15 downto 8);
+  signal dst : bit_vector(7 downto 0);
+begin
+  process
+    variable tmp : bit_vector(3 downto 0);
+  begin
+    dst <= src;
+    tmp := dst(3 downto 0);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn signal_read_before_assignment_relies_on_default() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::DefaultInitialValue);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+  signal toggle : bit;
+begin
+  process (clk)
+  begin
+    if clk'event and clk = '1' then
+      toggle <= not toggle;
+    end if;
+  end process;
+
+  q <= toggle;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s("toggle", 3),
+            "Signal 'toggle' is read before being assigned and relies on the default initial value",
+        )
+        .related(code.s1("toggle"), "Defined here")],
+    );
+}
+
+#[test]
+fn signal_read_but_never_assigned_relies_on_default() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::DefaultInitialValue);
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+  port (o : out bit);
+end entity;
+
+architecture a of child is
+begin
+  o <= '1';
+end architecture;
+
+entity ent is
+  port (clk : in bit; q, q2 : out bit);
+end entity;
+
+architecture a of ent is
+  signal undriven : bit;
+  signal connected : bit;
+begin
+  process (clk)
+  begin
+    if clk'event and clk = '1' then
+      q <= undriven;
+    end if;
+  end process;
+
+  q2 <= undriven or connected;
+
+  inst : entity work.child port map (o => connected);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s("undriven", 2),
+            "Signal 'undriven' is read but never assigned and relies on the default initial value",
+        )
+        .related(code.s1("undriven"), "Defined here")],
+    );
+}
+
+#[test]
+fn signal_with_initial_value_or_reset_does_not_rely_on_default() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::DefaultInitialValue);
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (clk, rst : in bit; q, q2 : out bit);
+end entity;
+
+architecture a of ent is
+  signal toggle : bit := '0';
+  signal toggle2 : bit;
+begin
+  process (clk)
+  begin
+    if clk'event and clk = '1' then
+      toggle <= not toggle;
+    end if;
+  end process;
+
+  process (clk, rst)
+  begin
+    if rst = '1' then
+      toggle2 <= '0';
+    elsif clk'event and clk = '1' then
+      toggle2 <= not toggle2;
+    end if;
+  end process;
+
+  q <= toggle;
+  q2 <= toggle2;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn resolved_signal_driven_by_process_and_concurrent_assignment() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::ProcessAndConcurrentDriver);
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  function resolved (s : std_ulogic_vector) return std_ulogic;
+  subtype std_logic is resolved std_ulogic;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal bus_bit : std_logic;
+  signal other_bit : std_logic;
+begin
+  bus_bit <= 'Z';
+  other_bit <= 'Z';
+  other_bit <= '1';
+
+  process
+  begin
+    bus_bit <= '1';
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::warning(
+            code.s1("bus_bit"),
+            "Signal 'bus_bit' is driven both by a process and by a concurrent assignment",
+        )
+        .related(code.s("bus_bit", 3), "Driven by process here")
+        .related(code.s("bus_bit", 2), "Driven by concurrent assignment here")],
+    );
+}
+
+#[test]
+fn unresolved_signal_driven_by_process_and_concurrent_assignment_is_only_an_error() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lint(Lint::ProcessAndConcurrentDriver);
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal flag : bit;
+begin
+  flag <= '0';
+
+  process
+  begin
+    flag <= '1';
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("flag"),
+            "Signal 'flag' of unresolved subtype 'BIT' has multiple drivers",
+        )
+        .related(code.s("flag", 2), "Driven here")
+        .related(code.s("flag", 3), "Driven here")],
+    );
+}
@@ -7,15 +7,19 @@
 mod circular_dependencies;
 mod context_clause;
 mod deferred_constant;
+mod diagnostic_order;
 mod homographs;
 mod implicit;
 mod incomplete_type;
 mod incremental_analysis;
+mod index;
+mod lint;
 mod package_instance;
 mod protected_type;
 mod resolves_design_units;
 mod resolves_names;
 mod resolves_type_mark;
+mod typecheck;
 mod util;
 mod visibility;
 
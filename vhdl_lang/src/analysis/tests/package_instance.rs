@@ -67,3 +67,58 @@ end package;
         ],
     );
 }
+
+#[test]
+fn use_clause_selects_package_instance_nested_in_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+  generic (width : natural);
+  constant member : natural := width;
+end package;
+
+package outer is
+  package inner is new work.gpkg generic map (width => 8);
+end package;
+
+use work.outer.inner.member;
+use work.outer.inner.all;
+
+package user is
+  constant c0 : natural := member;
+  constant c1 : natural := work.outer.inner.member;
+end package;
+
+use work.outer.inner.missing;
+
+package user2 is
+  constant c2 : natural := work.outer.inner.missing2;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("missing"),
+                "No declaration of 'missing' within package instance 'inner'",
+            ),
+            Diagnostic::error(
+                code.s1("missing2"),
+                "No declaration of 'missing2' within package instance 'inner'",
+            ),
+        ],
+    );
+
+    let member_pos = code.s1("member").pos();
+    for occurence in 2..=4 {
+        assert_eq!(
+            root.search_reference(code.source(), code.s("member", occurence).start()),
+            Some(member_pos.clone())
+        );
+    }
+}
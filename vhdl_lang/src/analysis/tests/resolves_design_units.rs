@@ -6,6 +6,7 @@
 
 use super::resolves_type_mark::kind_error;
 use super::*;
+use crate::ast::{PrimaryKind, SecondaryKind, UnitId};
 
 #[test]
 fn error_on_configuration_before_entity_in_same_file() {
@@ -502,3 +503,389 @@ end architecture;
         )],
     );
 }
+
+#[test]
+fn entity_of_architecture_and_architectures_of_entity() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+architecture sim of ent is
+begin
+end architecture;
+
+entity other is
+end entity;
+
+architecture rtl of other is
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let lib = code.symbol("libname");
+    let ent = UnitId::primary(&lib, PrimaryKind::Entity, &code.symbol("ent"));
+    let rtl = UnitId::secondary(
+        &lib,
+        SecondaryKind::Architecture,
+        &code.symbol("ent"),
+        &code.symbol("rtl"),
+    );
+    let sim = UnitId::secondary(
+        &lib,
+        SecondaryKind::Architecture,
+        &code.symbol("ent"),
+        &code.symbol("sim"),
+    );
+
+    assert_eq!(root.entity_of_architecture(&rtl), Some(ent.clone()));
+    assert_eq!(root.entity_of_architecture(&sim), Some(ent.clone()));
+    assert_eq!(root.entity_of_architecture(&ent), None);
+    assert_eq!(root.architectures_of_entity(&ent), vec![rtl, sim]);
+}
+
+#[test]
+fn package_body_of_package_and_package_of_body() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant deferred : natural;
+end package;
+
+package other is
+end package;
+",
+    );
+
+    builder.code(
+        "libname",
+        "
+package body pkg is
+  constant deferred : natural := 0;
+end package body;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let lib = code.symbol("libname");
+    let pkg = UnitId::package(&lib, &code.symbol("pkg"));
+    let body = UnitId::secondary(
+        &lib,
+        SecondaryKind::PackageBody,
+        &code.symbol("pkg"),
+        &code.symbol("pkg"),
+    );
+    let other = UnitId::package(&lib, &code.symbol("other"));
+
+    assert_eq!(root.package_body_of(&pkg), Some(body.clone()));
+    assert_eq!(root.package_decl_of(&body), Some(pkg.clone()));
+    assert_eq!(root.package_body_of(&other), None);
+    assert_eq!(root.package_body_of(&body), None);
+    assert_eq!(root.package_decl_of(&pkg), None);
+}
+
+#[test]
+fn error_on_entity_instance_from_missing_library() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity ent2 is
+end entity;
+
+library nolib;
+
+architecture a of ent2 is
+begin
+  inst : entity nolib.ent;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("nolib", 1),
+            "No such library 'nolib'",
+        )],
+    );
+}
+
+#[test]
+fn error_on_entity_instance_from_missing_library_without_library_clause() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
+begin
+  inst : entity nolib.ent;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("nolib"),
+            "No such library 'nolib'",
+        )],
+    );
+}
+
+#[test]
+fn error_on_entity_instance_missing_from_library() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+end entity;
+
+library libname;
+
+architecture a of ent2 is
+begin
+  inst : entity libname.noent;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("noent"),
+            "No primary unit 'noent' within library 'libname'",
+        )],
+    );
+}
+
+#[test]
+fn error_on_entity_instance_from_library_which_is_not_visible() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "otherlib",
+        "
+entity ent is
+end entity;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
+begin
+  inst : entity otherlib.ent;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("otherlib"),
+            "Library 'otherlib' is not visible, missing library clause 'library otherlib;'",
+        )],
+    );
+}
+
+#[test]
+fn rename_architecture_updates_configuration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture rtl of ent is
+begin
+end architecture;
+
+entity ent2 is
+end entity;
+
+architecture rtl of ent2 is
+begin
+end architecture;
+
+configuration cfg of ent is
+for rtl
+end for;
+end configuration;
+
+configuration cfg2 of ent2 is
+for rtl
+end for;
+end configuration;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.rename(&code.s("rtl", 1).pos()),
+        vec![code.s("rtl", 1).pos(), code.s("rtl", 3).pos()]
+    );
+    assert_eq!(
+        root.rename(&code.s("rtl", 2).pos()),
+        vec![code.s("rtl", 2).pos(), code.s("rtl", 4).pos()]
+    );
+}
+
+#[test]
+fn units_in_source() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+",
+    );
+    let other = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let lib = code.symbol("libname");
+    let ent = UnitId::primary(&lib, PrimaryKind::Entity, &code.symbol("ent"));
+    let arch = UnitId::secondary(
+        &lib,
+        SecondaryKind::Architecture,
+        &code.symbol("ent"),
+        &code.symbol("a"),
+    );
+    let pkg = UnitId::primary(&lib, PrimaryKind::Package, &code.symbol("pkg"));
+
+    assert_eq!(root.units_in_source(code.source()), vec![ent, arch]);
+    assert_eq!(root.units_in_source(other.source()), vec![pkg]);
+}
+
+#[test]
+fn units_in_source_include_duplicates() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package pkg is
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("pkg", 2),
+            "A primary unit has already been declared with name 'pkg' in library 'libname'",
+        )
+        .related(code.s("pkg", 1), "Previously defined here")],
+    );
+
+    let pkg = UnitId::primary(
+        &code.symbol("libname"),
+        PrimaryKind::Package,
+        &code.symbol("pkg"),
+    );
+    assert_eq!(root.units_in_source(code.source()), vec![pkg.clone(), pkg]);
+}
+
+#[test]
+fn edit_impact() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : natural;
+end package;
+
+package body pkg is
+  constant c : natural := 0;
+end package body;
+",
+    );
+    let user = builder.code(
+        "libname",
+        "
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant d : natural := c;
+begin
+end architecture;
+",
+    );
+    let unrelated = builder.code(
+        "libname",
+        "
+package other is
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let lib = code.symbol("libname");
+    let pkg = UnitId::primary(&lib, PrimaryKind::Package, &code.symbol("pkg"));
+    let ent = UnitId::primary(&lib, PrimaryKind::Entity, &code.symbol("ent"));
+    assert!(root.dependents_of(&pkg).contains(&ent));
+
+    let impact = root.edit_impact(code.source());
+    assert_eq!(impact.reparse, vec![code.source().clone()]);
+    assert_eq!(impact.reanalyze, vec![user.source().clone()]);
+
+    let impact = root.edit_impact(unrelated.source());
+    assert_eq!(impact.reparse, vec![unrelated.source().clone()]);
+    assert!(impact.reanalyze.is_empty());
+}
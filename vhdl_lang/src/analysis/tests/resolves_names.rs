@@ -5,6 +5,9 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::analysis::RenameConflict;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[test]
 fn resolves_names_in_object_decl_init_expressions() {
@@ -200,9 +203,9 @@ fn search_names_in_discrete_ranges() {
     check_search_reference(
         "
 package pkg is
-  constant decl : natural := 0;
-  type arr_t is array (natural range decl to decl) of natural;
-  type arr2_t is array (decl to decl) of natural;
+  constant decl : integer_vector(0 to 0) := (others => 0);
+  type arr_t is array (natural range decl(0) to decl(0)) of natural;
+  type arr2_t is array (decl(0) to decl(0)) of natural;
   type arr3_t is array (decl'range) of natural;
 end package;
 ",
@@ -856,7 +859,9 @@ architecture a of ent is
   begin
   end;
 
-  signal decl : natural := 0;
+  -- Resolved since it has multiple drivers
+  function resolve(values : integer_vector) return natural;
+  signal decl : resolve natural := 0;
 begin
   decl <= decl;
   decl <= decl when decl = 0 else decl;
@@ -1170,3 +1175,358 @@ end package body;
         Some(code.s("subpgm", 1).pos())
     );
 }
+
+#[test]
+fn resolves_call_by_full_path_without_use_clause() {
+    let mut builder = LibraryBuilder::new();
+    let ieee = builder.code(
+        "ieee",
+        "
+package numeric_std is
+  type unsigned is array (natural range <>) of bit;
+  type signed is array (natural range <>) of bit;
+  function to_integer(arg : unsigned) return natural;
+  function to_integer(arg : signed) return integer;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal u : ieee.numeric_std.unsigned(7 downto 0);
+begin
+  process
+    variable i : integer;
+  begin
+    i := ieee.numeric_std.to_integer(u);
+    i := ieee.numeric_std.to_integer(i);
+    i := ieee.numeric_std.missing(u);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("to_integer", 2),
+                "No declaration of 'to_integer' for type 'INTEGER'",
+            )
+            .related(ieee.s("to_integer", 1), "Defined here")
+            .related(ieee.s("to_integer", 2), "Defined here"),
+            Diagnostic::error(
+                code.s1("missing"),
+                "No declaration of 'missing' within package 'numeric_std'",
+            ),
+        ],
+    );
+
+    assert_eq!(
+        root.search_reference(code.source(), code.s1("to_integer").end()),
+        Some(ieee.s1("to_integer").pos())
+    );
+}
+
+#[test]
+fn resolves_indexed_call_by_full_path_of_parameterless_function() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function get return bit_vector;
+  function get(arg : bit) return bit_vector;
+end package;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable b : bit;
+  begin
+    b := work.pkg.get(0);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference(code.source(), code.s("get", 3).end()),
+        Some(code.s1("get").pos())
+    );
+}
+
+#[test]
+fn resolves_record_aggregate_choices_to_fields() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    field_a : natural;
+    field_b : boolean;
+  end record;
+
+  constant c0 : rec_t := (field_a => 0, field_b => false);
+  constant c1 : rec_t := rec_t'(field_b => true, field_a => 1);
+  constant c2 : rec_t := (2, field_b => true);
+  constant c3 : rec_t := (field_a => 3, others => false);
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference(code.source(), code.s("field_b", 3).start()),
+        Some(code.s1("field_b").pos())
+    );
+    assert_eq!(
+        root.find_all_references(&code.s1("field_a").pos()),
+        vec![
+            code.s("field_a", 1).pos(),
+            code.s("field_a", 2).pos(),
+            code.s("field_a", 3).pos(),
+            code.s("field_a", 4).pos()
+        ]
+    );
+}
+
+#[test]
+fn error_on_unknown_or_duplicate_record_aggregate_choice() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    field_a : natural;
+    field_b : boolean;
+  end record;
+
+  constant c0 : rec_t := (field_a => 0, field_c => false);
+  constant c1 : rec_t := (field_a => 0, field_b => false, field_a => 1);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("field_c"),
+                "No declaration of 'field_c' within type 'rec_t'",
+            ),
+            Diagnostic::error(
+                code.s1("(field_a => 0, field_c => false)"),
+                "Missing association of record field 'field_b'",
+            ),
+            Diagnostic::error(
+                code.s("field_a", 4),
+                "Record field 'field_a' is already associated",
+            )
+            .related(code.s("field_a", 3), "Previously chosen here"),
+        ],
+    );
+}
+
+#[test]
+fn rename_preview_lists_conflicts_of_using_scopes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant width : natural := 8;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant depth : natural := 4;
+  signal sig : bit_vector(width - 1 downto 0);
+begin
+end architecture;
+
+use work.pkg.all;
+
+entity ent2 is
+end entity;
+
+architecture a of ent2 is
+  signal sig : bit_vector(width - 1 downto 0);
+begin
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let preview = root.rename_preview(&code.s1("width").pos(), "depth");
+    assert_eq!(
+        preview.edits,
+        vec![
+            code.s("width", 1).pos(),
+            code.s("width", 2).pos(),
+            code.s("width", 3).pos()
+        ]
+    );
+    assert_eq!(
+        preview.conflicts,
+        vec![RenameConflict {
+            pos: code.s("width", 2).pos(),
+            decl_pos: code.s1("depth").pos(),
+        }]
+    );
+
+    // There are no conflicts for an unused name
+    let preview = root.rename_preview(&code.s1("width").pos(), "size");
+    assert_eq!(preview.edits.len(), 3);
+    assert_eq!(preview.conflicts, vec![]);
+}
+
+#[test]
+fn rename_preview_lists_conflicts_of_local_declarations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  main : process
+    variable count : natural := 0;
+    variable total : natural := 0;
+  begin
+    count := count + 1;
+    total := total + count;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let preview = root.rename_preview(&code.s1("count").pos(), "total");
+    assert_eq!(preview.edits.len(), 4);
+    assert_eq!(
+        preview.conflicts,
+        vec![RenameConflict {
+            pos: code.s1("count").pos(),
+            decl_pos: code.s1("total").pos(),
+        }]
+    );
+
+    let preview = root.rename_preview(&code.s1("count").pos(), "size");
+    assert_eq!(preview.conflicts, vec![]);
+}
+
+#[test]
+fn resolves_names_against_custom_standard_library() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : integer := 0;
+  constant c1 : vendor_t := 0;
+end package;
+",
+    );
+
+    let standard = CodeBuilder {
+        symbols: builder.symbols(),
+    }
+    .code_with_file_name(
+        Path::new("vendor_standard.vhd"),
+        "
+package standard is
+  type boolean is (false, true);
+  type bit is ('0', '1');
+  type character is (nul, ' ', 'a');
+  type integer is range -2147483647 to 2147483647;
+  type vendor_t is range 0 to 7;
+end package;
+",
+    );
+
+    let mut root = builder.get_unanalyzed_root();
+    let mut diagnostics = Vec::new();
+    root.add_standard_library_from(&[standard.source().clone()], &mut diagnostics);
+    root.analyze(&mut diagnostics);
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference(code.source(), code.s1("integer").start()),
+        Some(standard.s1("integer").pos())
+    );
+    assert_eq!(
+        root.search_reference(code.source(), code.s1("vendor_t").start()),
+        Some(standard.s1("vendor_t").pos())
+    );
+}
+
+#[test]
+fn find_references_streaming_stops_when_cancelled() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant decl : natural := 0;
+  constant c0 : natural := decl;
+  constant c1 : natural := decl + c0;
+  constant c2 : natural := decl + c1;
+end package;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let decl_pos = code.s1("decl").pos();
+    let all_references = root.find_all_references(&decl_pos);
+    assert_eq!(all_references.len(), 4);
+
+    let cancel = AtomicBool::new(false);
+    let mut references = Vec::new();
+    root.find_references_streaming(&decl_pos, &mut |pos| references.push(pos.clone()), &cancel);
+    assert_eq!(references, all_references);
+
+    let mut references = Vec::new();
+    root.find_references_streaming(
+        &decl_pos,
+        &mut |pos| {
+            references.push(pos.clone());
+            cancel.store(true, Ordering::Relaxed);
+        },
+        &cancel,
+    );
+    assert_eq!(references.len(), 1);
+    assert!(all_references.contains(&references[0]));
+}
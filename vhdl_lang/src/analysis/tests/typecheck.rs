@@ -0,0 +1,2072 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn wait_statement_with_valid_clauses() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+  signal cond : boolean;
+  constant delay : time := 5 ns;
+begin
+  process
+  begin
+    wait on sig until cond for 10 ns;
+    wait until sig = '1' and cond for delay * 2;
+    wait until sig;
+    wait until sig'event for 1 ns + delay;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn wait_until_must_be_boolean() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : integer;
+begin
+  process
+  begin
+    wait until sig + 1;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("sig + 1"),
+            "Expected type 'BOOLEAN', got type 'INTEGER'",
+        )],
+    );
+}
+
+#[test]
+fn wait_until_accepts_type_with_condition_operator() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type logic is ('0', '1');
+  function \"??\"(value : logic) return boolean;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : logic;
+begin
+  process
+  begin
+    wait until sig;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn wait_for_must_be_time() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant int : natural := 0;
+begin
+  process
+  begin
+    wait for 5;
+    wait for int;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("wait for 5").s1("5"),
+                "Expected type 'TIME', got universal_integer",
+            ),
+            Diagnostic::error(
+                code.s1("wait for int").s1("int"),
+                "Expected type 'TIME', got type 'INTEGER'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn wait_on_must_be_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+  constant const : bit := '0';
+begin
+  process
+  begin
+    wait on sig, const;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("sig, const").s1("const"),
+            "Expected signal, got constant 'const'",
+        )],
+    );
+}
+
+#[test]
+fn port_width_mismatch_in_entity_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (d : in bit_vector(7 downto 0); q : out bit_vector(0 to 3));
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  subtype byte_t is bit_vector(7 downto 0);
+  signal byte : byte_t;
+  signal nibble : bit_vector(3 downto 0);
+  signal narrow : bit_vector(2 downto 0);
+begin
+  good_inst : entity work.ent port map (d => byte, q => nibble);
+  bad_inst : entity work.ent port map (d => nibble, q => narrow);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("nibble", 3),
+                "Expected width 8 for port 'd', got width 4",
+            ),
+            Diagnostic::error(
+                code.s("narrow", 2),
+                "Expected width 4 for port 'q', got width 3",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn width_of_array_with_range_of_unrepresentable_length() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (d : in bit_vector(-9223372036854775807 to 9223372036854775807));
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal wide : bit_vector(-9223372036854775807 to 9223372036854775807);
+begin
+  inst : entity work.ent port map (d => wide);
+end architecture;
+",
+    );
+
+    builder.analyze();
+}
+
+#[test]
+fn positional_associations_in_entity_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : natural; flag : boolean);
+  port (clk : in bit; count : out integer);
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk0, clk1, clk2 : bit;
+  signal count0, count1, count2 : integer;
+  signal extra : bit;
+begin
+  good_inst : entity work.ent generic map (8, true) port map (clk0, count0);
+  many_inst : entity work.ent generic map (8, true) port map (clk1, count1, extra);
+  swapped_inst : entity work.ent generic map (8, true) port map (count2, clk2);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("extra", 2),
+                "Too many positional actuals, entity 'ent' has 2 ports",
+            ),
+            Diagnostic::error(
+                code.s("count2", 2),
+                "Expected type 'BIT', got type 'INTEGER'",
+            ),
+            Diagnostic::error(code.s("clk2", 2), "Expected type 'INTEGER', got type 'BIT'"),
+        ],
+    );
+}
+
+#[test]
+fn positional_associations_with_formals_of_unresolved_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (width : missing_t; flag : boolean);
+  port (clk : in missing_t; count : out integer);
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk0 : bit;
+begin
+  inst : entity work.ent generic map (8, 1) port map (clk0, clk0);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("missing_t", 1), "No declaration of 'missing_t'"),
+            Diagnostic::error(code.s("missing_t", 2), "No declaration of 'missing_t'"),
+            Diagnostic::error(
+                code.s1("1)").s1("1"),
+                "Expected type 'BOOLEAN', got universal_integer",
+            ),
+            Diagnostic::error(code.s("clk0", 3), "Expected type 'INTEGER', got type 'BIT'"),
+        ],
+    );
+}
+
+#[test]
+fn positional_association_after_named_association() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+  port (a, b : in bit);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig, sig2 : bit;
+begin
+  bad : entity work.child port map (a => sig, sig2);
+  positional : entity work.child port map (sig, sig2);
+  named : entity work.child port map (a => sig, b => sig2);
+  mixed : entity work.child port map (sig, b => sig2);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("a => sig, sig2").s1("sig2"),
+            "Positional association not allowed after named association",
+        )],
+    );
+}
+
+#[test]
+fn array_aggregate_range_choices_within_bounds() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : bit_vector(0 to 7) := (0 to 3 => '0', 4 to 7 => '1');
+  constant c1 : bit_vector(7 downto 0) := (7 downto 4 => '0', 3 downto 1 => '1', 0 => '0');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn array_aggregate_range_choice_outside_of_bounds() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : bit_vector(0 to 7) := (0 to 3 => '0', 4 to 8 => '1');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("4 to 8"),
+            "Choice 4 to 8 is outside of index range 0 to 7",
+        )],
+    );
+}
+
+#[test]
+fn array_aggregate_overlapping_range_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : bit_vector(0 to 7) := (0 to 4 => '0', 3 to 7 => '1');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("3 to 7"),
+            "Choice 3 to 7 overlaps with a previous choice",
+        )
+        .related(code.s1("0 to 4"), "Previously chosen here")],
+    );
+}
+
+#[test]
+fn expression_at_cursor_is_smallest_enclosing_expression() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant a, b, c : integer := 0;
+  constant d : integer := a + b * c;
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let (pos, type_info) = root
+        .expression_at(code.source(), code.s1("*").start())
+        .unwrap();
+    assert_eq!(pos, code.s1("b * c").pos());
+    assert_eq!(type_info.name, "INTEGER");
+    assert!(type_info.decl_pos.is_some());
+
+    assert_eq!(
+        root.expression_at(code.source(), code.s1("pkg").start()),
+        None
+    );
+}
+
+#[test]
+fn expression_at_cursor_is_typed_in_innermost_region() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal shadowed : bit;
+begin
+  main : process
+    variable count : natural;
+    variable shadowed : boolean;
+  begin
+    count := count + 1;
+    shadowed := not shadowed;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let (pos, type_info) = root
+        .expression_at(code.source(), code.s1("count + 1").start())
+        .unwrap();
+    assert_eq!(pos, code.s1("count + 1").s1("count").pos());
+    assert_eq!(type_info.name, "INTEGER");
+
+    let (pos, type_info) = root
+        .expression_at(
+            code.source(),
+            code.s1("not shadowed").s1("shadowed").start(),
+        )
+        .unwrap();
+    assert_eq!(pos, code.s1("not shadowed").s1("shadowed").pos());
+    assert_eq!(type_info.name, "BOOLEAN");
+}
+
+#[test]
+fn assert_severity_of_severity_level() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  assert false severity failure;
+
+  process
+  begin
+    assert false report \"msg\" severity error;
+    report \"msg\" severity note;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn assert_severity_of_wrong_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+  begin
+    assert false severity 3;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("3"),
+            "Expected type 'SEVERITY_LEVEL', got universal_integer",
+        )],
+    );
+}
+
+#[test]
+fn report_statement_of_string() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant prefix : string := \"value \";
+begin
+  process
+  begin
+    report \"message\" severity note;
+    report prefix & integer'image(1);
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn report_of_wrong_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  assert false report 1;
+
+  process
+  begin
+    report 2 severity note;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("1"),
+                "Expected type 'STRING', got universal_integer",
+            ),
+            Diagnostic::error(
+                code.s1("2"),
+                "Expected type 'STRING', got universal_integer",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn severity_literal_overloaded_by_local_enum_literal() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  type state_t is (ok, error);
+
+  procedure check(value : boolean) is
+  begin
+    report \"check failed\" severity error;
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn condition_literal_overloaded_by_local_enum_literal() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  main : process
+    type state_t is (false, other);
+    variable state : state_t := false;
+  begin
+    wait until false;
+    state := other;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn assignment_to_for_loop_parameter() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable v : natural;
+  begin
+    for i in 0 to 3 loop
+      i := 0;
+      v := i;
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("i := 0").s1("i"),
+            "Constant 'i' may not be the target of an assignment",
+        )],
+    );
+}
+
+#[test]
+fn for_loop_parameter_is_not_visible_after_loop() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable v : natural;
+  begin
+    for idx in 0 to 3 loop
+      v := idx;
+    end loop;
+    v := idx;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("idx", 3),
+            "No declaration of 'idx'",
+        )],
+    );
+}
+
+#[test]
+fn force_assignment_of_signal_type() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  process
+  begin
+    sig <= force '1';
+    sig <= release;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn force_assignment_of_wrong_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  process
+  begin
+    sig <= force 1;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("force 1").s1("1"),
+            "Expected type 'BIT', got universal_integer",
+        )],
+    );
+}
+
+#[test]
+fn force_mode_out_of_port_of_mode_in() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (p : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+begin
+  process
+  begin
+    p <= force in '1';
+    p <= force out '1';
+    q <= force out '1';
+    p <= release out;
+    p <= release;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("p <= force out").s1("p"),
+                "Force mode 'out' is not allowed for signal 'p' of mode 'in'",
+            ),
+            Diagnostic::error(
+                code.s1("p <= release out").s1("p"),
+                "Force mode 'out' is not allowed for signal 'p' of mode 'in'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn error_on_multiple_drivers_of_unresolved_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy);
+  signal state : state_t;
+begin
+  state <= idle;
+
+  process
+  begin
+    state <= busy;
+    wait;
+    state <= idle;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("state", 2),
+            "Signal 'state' of unresolved subtype 'state_t' has multiple drivers",
+        )
+        .related(code.s("state", 4), "Driven here")
+        .related(code.s("state", 5), "Driven here")],
+    );
+}
+
+#[test]
+fn multiple_drivers_of_resolved_signal() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  function resolved (s : std_ulogic_vector) return std_ulogic;
+  subtype std_logic is resolved std_ulogic;
+  subtype std_logic_vector is (resolved) std_ulogic_vector;
+end package;
+",
+    );
+    builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal bus_bit : std_logic;
+  signal bus_vec : std_logic_vector(7 downto 0);
+  signal parts : std_ulogic_vector(1 downto 0);
+begin
+  bus_bit <= 'Z';
+  bus_bit <= '1';
+  bus_vec <= (others => 'Z');
+  bus_vec <= (others => '0');
+
+  -- Assignments to different elements are not multiple drivers of the whole signal
+  parts(0) <= '0';
+  parts(1) <= '1';
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_overlapping_slice_drivers_of_unresolved_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal vec : bit_vector(7 downto 0);
+begin
+  vec(7 downto 4) <= \"0000\";
+  vec(5 downto 0) <= \"000000\";
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("vec"),
+            "Signal 'vec' of unresolved subtype 'BIT_VECTOR' has multiple drivers",
+        )
+        .related(code.s1("vec(7 downto 4)"), "Driven here")
+        .related(code.s1("vec(5 downto 0)"), "Driven here")],
+    );
+}
+
+#[test]
+fn error_on_multiple_drivers_of_element_of_unresolved_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : bit_vector(1 downto 0);
+begin
+  s(0) <= '0';
+  s(0) <= '1';
+  s(1) <= '1';
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("s :").s1("s"),
+            "Signal 's' of unresolved subtype 'BIT_VECTOR' has multiple drivers",
+        )
+        .related(code.s1("s(0) <= '0'").s1("s(0)"), "Driven here")
+        .related(code.s1("s(0) <= '1'").s1("s(0)"), "Driven here")],
+    );
+}
+
+#[test]
+fn non_overlapping_slice_drivers_of_unresolved_signal() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal vec : bit_vector(7 downto 0);
+begin
+  vec(7 downto 4) <= \"0000\";
+  vec(3) <= '0';
+
+  process
+  begin
+    vec(2 downto 0) <= \"000\";
+    wait;
+    vec(0) <= '1';
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_overlapping_slice_drivers_with_constant_bounds() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant H : natural := 4;
+  signal vec : bit_vector(7 downto 0);
+begin
+  vec(H downto 0) <= (others => '0');
+  vec(7 downto H) <= (others => '1');
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("vec"),
+            "Signal 'vec' of unresolved subtype 'BIT_VECTOR' has multiple drivers",
+        )
+        .related(code.s1("vec(H downto 0)"), "Driven here")
+        .related(code.s1("vec(7 downto H)"), "Driven here")],
+    );
+}
+
+#[test]
+fn drivers_of_unresolved_signal_with_non_static_bounds() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (N : natural := 4);
+end entity;
+
+architecture a of ent is
+  constant H : natural := 4;
+  signal a, b, c : bit_vector(7 downto 0);
+  signal d : bit_vector(2 * N - 1 downto 0);
+begin
+  g1 : for k in 0 to 3 generate
+    b(k) <= a(k);
+  end generate;
+
+  g2 : for k in 4 to 7 generate
+    b(k) <= not a(k);
+  end generate;
+
+  c(H - 1 downto 0) <= a(H - 1 downto 0);
+  c(7 downto H) <= a(7 downto H);
+
+  d(N - 1 downto 0) <= (others => '0');
+  d(2 * N - 1 downto N) <= (others => '1');
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_conditional_initial_value_without_else() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant wide : boolean := true;
+  constant width : natural := 16 when wide;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("16 when wide"),
+            "Conditional expression must end with 'else' when used as a value",
+        )],
+    );
+}
+
+#[test]
+fn conditional_initial_value_with_else() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant wide : boolean := true;
+  constant width : natural := 16 when wide else 8;
+  constant depth : natural := 4 when not wide else 2 when width = 8 else 1;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn condition_operator_returns_boolean() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  subtype std_logic is std_ulogic;
+  subtype std_logic_vector is std_ulogic_vector;
+  function \"??\"(l : std_ulogic) return boolean;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sl : std_logic;
+begin
+  process
+  begin
+    if ?? sl then
+      wait for ?? sl;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("wait for ?? sl").s1("?? sl"),
+            "Expected type 'TIME', got type 'BOOLEAN'",
+        )],
+    );
+}
+
+#[test]
+fn matching_equality_of_std_logic_vectors() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  subtype std_logic is std_ulogic;
+  subtype std_logic_vector is std_ulogic_vector;
+  function \"??\"(l : std_ulogic) return boolean;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal a, b : std_logic_vector(0 to 1);
+begin
+  process
+  begin
+    if a ?= b then
+      wait for a ?/= b;
+    end if;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("a ?/= b"),
+            "Expected type 'TIME', got type 'std_ulogic'",
+        )],
+    );
+}
+
+#[test]
+fn error_on_matching_operator_with_incompatible_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant a : integer := 0;
+  constant b : bit := '0';
+  constant c : bit := a ?= a;
+  constant d : bit := b ?< b;
+  constant e : boolean := ?? a;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("a ?= a"),
+                "No declaration of operator \"?=\" for type 'INTEGER' and type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1("?? a"),
+                "No declaration of operator \"??\" for type 'INTEGER'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn attribute_prefix_must_be_of_attribute_class() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type arr_t is array (0 to 3) of bit;
+  type rec_t is record
+    field : bit;
+  end record;
+  signal sig : bit;
+  constant arr : arr_t := (others => '0');
+  constant rec : rec_t := (field => '0');
+begin
+  process
+    variable int : integer;
+  begin
+    wait until sig'event;
+    wait until arr_t'event;
+    int := arr'length;
+    int := rec'length;
+    int := integer'pos(int);
+    int := int'pos(0);
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("arr_t'event").s1("arr_t"),
+                "Expected signal prefix for attribute 'event', got type 'arr_t'",
+            ),
+            Diagnostic::error(
+                code.s1("rec'length").s1("rec"),
+                "Expected array prefix for attribute 'length', got constant 'rec'",
+            ),
+            Diagnostic::error(
+                code.s1("int'pos").s1("int"),
+                "Expected type prefix for attribute 'pos', got variable 'int'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn concatenation_requires_compatible_element_types() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  subtype std_logic is std_ulogic;
+  subtype std_logic_vector is std_ulogic_vector;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal bit0 : std_logic;
+  signal low, high : std_logic_vector(3 downto 0);
+  signal word, other : std_logic_vector(7 downto 0);
+  signal wide : std_logic_vector(8 downto 0);
+  signal int : integer;
+begin
+  word <= high & low;
+  wide <= bit0 & word;
+  other <= low & int;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("low & int"),
+            "No declaration of operator \"&\" for type 'std_ulogic_vector' and type 'INTEGER'",
+        )],
+    );
+}
+
+#[test]
+fn port_width_of_concatenation() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (d : in bit_vector(7 downto 0));
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal nibble : bit_vector(3 downto 0);
+  signal narrow : bit_vector(2 downto 0);
+  signal b : bit;
+begin
+  good_inst : entity work.ent port map (d => nibble & narrow & b);
+  bad_inst : entity work.ent port map (d => nibble & narrow);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("nibble & narrow", 2),
+            "Expected width 8 for port 'd', got width 7",
+        )],
+    );
+}
+
+#[test]
+fn passive_procedure_call_in_entity_statement_part() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  procedure monitor(signal clk : in bit);
+end package;
+
+package body pkg is
+  procedure monitor(signal clk : in bit) is
+  begin
+    assert clk = '0' or clk = '1';
+  end procedure;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+  port (clk : in bit);
+begin
+  monitor_call : monitor(clk);
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_active_procedure_call_in_entity_statement_part() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure drive(signal sig : out bit);
+end package;
+
+package body pkg is
+  procedure drive(signal sig : out bit) is
+  begin
+    sig <= '1';
+  end procedure;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+  port (sig : out bit);
+begin
+  drive(sig);
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("drive", 3),
+            "Active procedure call of 'drive' is not allowed in an entity statement part",
+        )
+        .related(code.s1("drive"), "Defined here")],
+    );
+}
+
+#[test]
+fn reject_inertial_delay_mechanism() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig, sig2, sig3 : bit;
+begin
+  sig <= reject 2 ns inertial '1' after 5 ns;
+  sig2 <= transport '0' after 1 ns;
+
+  process
+  begin
+    sig3 <= reject 1 ns inertial '1' after 1 ns, '0' after 2 ns;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_reject_exceeding_delay() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig, sig2 : bit;
+begin
+  sig <= reject 10 ns inertial '1' after 5 ns;
+  sig2 <= reject 2 inertial '1' after 5 ns;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("10 ns"),
+                "Pulse rejection limit must not exceed the delay of the first waveform element",
+            )
+            .related(code.s("5 ns", 1), "Delay of the first waveform element"),
+            Diagnostic::error(
+                code.s1("reject 2").s1("2"),
+                "Expected type 'TIME', got universal_integer",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn unresolved_type_of_signal_and_port_is_reported_once() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (p : in missing_t; q : out bit);
+end entity;
+
+architecture a of ent is
+  signal sig : missing_t;
+begin
+  sig <= p;
+  q <= p when sig = p else '0';
+
+  process
+  begin
+    wait until sig = p;
+    report sig;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("missing_t", 1), "No declaration of 'missing_t'"),
+            Diagnostic::error(code.s("missing_t", 2), "No declaration of 'missing_t'"),
+        ],
+    );
+}
+
+#[test]
+fn port_of_unresolved_type_keeps_its_position_in_port_list() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (a : in missing_t; b : in bit; c : out bit);
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal x, y, z : bit;
+begin
+  inst : entity work.ent port map (x, y, z);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("missing_t"),
+            "No declaration of 'missing_t'",
+        )],
+    );
+}
+
+#[test]
+fn generate_parameter_of_type_range_has_the_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type my_enum is (first, second);
+begin
+  gen : for i in my_enum generate
+    assert i = first report i;
+  end generate;
+
+  gen2 : for j in missing_t generate
+    assert j = first;
+  end generate;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("report i").s1("i"),
+                "Expected type 'STRING', got type 'my_enum'",
+            ),
+            Diagnostic::error(code.s1("missing_t"), "No declaration of 'missing_t'"),
+        ],
+    );
+
+    assert_eq!(
+        root.search_reference(code.source(), code.s1("i = first").start()),
+        Some(code.s1("for i").s1("i").pos())
+    );
+}
+
+#[test]
+fn error_on_case_alternative_after_others() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sel : natural;
+  signal sig : bit;
+begin
+  process
+  begin
+    case sel is
+      when 0 => sig <= '0';
+      when others => sig <= '1';
+      when 1 => sig <= '0';
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("when 1").s1("1"),
+            "Case alternative is unreachable after the 'others' choice",
+        )],
+    );
+}
+
+#[test]
+fn error_on_case_alternative_shadowed_by_previous_choices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done);
+  signal state : state_t;
+  signal sel : natural;
+  signal sig : bit;
+begin
+  process
+  begin
+    case sel is
+      when 0 to 3 => sig <= '0';
+      when 4 to 7 => sig <= '1';
+      when 2 to 5 => sig <= '0';
+      when 6 to 9 => sig <= '1';
+      when others => sig <= '0';
+    end case;
+
+    case state is
+      when idle | busy => sig <= '0';
+      when busy => sig <= '1';
+      when others => sig <= '0';
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("2 to 5"),
+                "Case alternative is unreachable, its choices are covered by previous choices",
+            )
+            .related(code.s1("0 to 3"), "Previously chosen here")
+            .related(code.s1("4 to 7"), "Previously chosen here"),
+            Diagnostic::error(
+                code.s("busy", 3),
+                "Case alternative is unreachable, its choices are covered by previous choices",
+            )
+            .related(code.s("busy", 2), "Previously chosen here"),
+        ],
+    );
+}
+
+#[test]
+fn string_literal_has_type_of_context() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  function resolved (s : std_ulogic_vector) return std_ulogic;
+  subtype std_logic is resolved std_ulogic;
+  subtype std_logic_vector is (resolved) std_ulogic_vector;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable v0 : std_logic_vector(2 downto 0);
+    variable v1 : bit_vector(2 downto 0);
+    variable v2 : string(1 to 5);
+    variable v3 : integer;
+  begin
+    v0 := \"1Z1\";
+    v1 := \"101\";
+    v2 := \"hello\";
+    v1 := \"1Z1\";
+    v3 := \"101\";
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("\"1Z1\"", 2),
+                "Expected type 'BIT_VECTOR', got string literal",
+            ),
+            Diagnostic::error(
+                code.s("\"101\"", 2),
+                "Expected type 'INTEGER', got string literal",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn error_on_ambiguous_string_literal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure proc(value : bit_vector);
+  procedure proc(value : string);
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+  begin
+    work.pkg.proc(\"101\");
+    work.pkg.proc(\"abc\");
+    proc(\"110\");
+    proc(\"def\");
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("\"101\""),
+                "Ambiguous string literal, it may be of type 'BIT_VECTOR' or 'STRING'",
+            ),
+            Diagnostic::error(
+                code.s1("\"110\""),
+                "Ambiguous string literal, it may be of type 'BIT_VECTOR' or 'STRING'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn error_on_static_integer_outside_of_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : integer := 2 ** 40;
+  constant c1 : natural := 2 ** 31;
+  constant c2 : integer := 2 ** 100;
+  constant c3 : integer := 2 ** 31 - 1;
+  constant c4 : integer := -(2 ** 30) * 2 + 1;
+  constant c5 : real := 2.0 ** 40;
+  constant c6 : natural := -1;
+  constant c7 : natural := c3 + 1;
+  constant c8 : integer range 0 to 3 := 4;
+  type small_t is range 0 to 7;
+  constant c9 : small_t := 8;
+  constant c10 : small_t := 7;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("2 ** 40"),
+                "Value 1099511627776 is outside of the range -2147483647 to 2147483647 of type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1("natural := 2 ** 31").s1("2 ** 31"),
+                "Value 2147483648 is outside of the range 0 to 2147483647 of subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("2 ** 100"),
+                "Overflow when evaluating static expression of type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1("-1"),
+                "Value -1 is outside of the range 0 to 2147483647 of subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1("c3 + 1"),
+                "Value 2147483648 is outside of the range 0 to 2147483647 of subtype 'NATURAL'",
+            ),
+            Diagnostic::error(
+                code.s1(":= 4").s1("4"),
+                "Value 4 is outside of the range 0 to 3 of type 'INTEGER'",
+            ),
+            Diagnostic::error(
+                code.s1(":= 8").s1("8"),
+                "Value 8 is outside of the range 0 to 7 of type 'small_t'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn name_attributes_are_strings_evaluated_for_design_units() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package Pkg is
+  constant path : string := pkg'path_name;
+end package;
+
+entity Ent is
+end entity;
+
+architecture rtl of ent is
+  signal sig : bit;
+begin
+  process
+  begin
+    report sig'path_name;
+    report ent'simple_name;
+    report ent'instance_name;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let (pos, type_info) = root
+        .expression_at(
+            code.source(),
+            code.s1("sig'path_name").s1("path_name").start(),
+        )
+        .unwrap();
+    assert_eq!(pos, code.s1("sig'path_name").pos());
+    assert_eq!(type_info.name, "STRING");
+    assert_eq!(
+        root.name_attribute_value(
+            code.source(),
+            code.s1("sig'path_name").s1("path_name").start()
+        ),
+        None
+    );
+
+    assert_eq!(
+        root.name_attribute_value(code.source(), code.s1("simple_name").start()),
+        Some("ent".to_owned())
+    );
+    assert_eq!(
+        root.name_attribute_value(code.source(), code.s1("instance_name").start()),
+        Some(":ent(rtl):".to_owned())
+    );
+    assert_eq!(
+        root.name_attribute_value(code.source(), code.s1("pkg'path_name").start()),
+        Some(":libname:pkg:".to_owned())
+    );
+}
+
+#[test]
+fn error_on_indexing_or_slicing_non_array_prefix() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+  type std_ulogic_vector is array (natural range <>) of std_ulogic;
+  subtype std_logic is std_ulogic;
+  subtype std_logic_vector is std_ulogic_vector;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal scalar : std_logic;
+  signal vec : std_logic_vector(7 downto 0);
+  signal count : natural;
+  signal lower : std_logic_vector(3 downto 0);
+  signal other : std_logic_vector(1 downto 0);
+begin
+  lower <= vec(7 downto 4);
+  vec(0) <= vec(1);
+  other <= scalar(1 downto 0);
+  scalar <= count(0);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("scalar", 2),
+                "Cannot slice signal 'scalar' of non-array type 'std_ulogic'",
+            ),
+            Diagnostic::error(
+                code.s("count", 2),
+                "Cannot index signal 'count' of non-array type 'INTEGER'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn static_range_constraint_with_constant_bound() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (d : in bit_vector(7 downto 0));
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  constant width : natural := 8;
+  constant narrow_width : natural := width - 1;
+  signal good : bit_vector(0 to width - 1);
+  signal bad : bit_vector(0 to narrow_width - 1);
+begin
+  good_inst : entity work.ent port map (d => good);
+  bad_inst : entity work.ent port map (d => bad);
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("bad", 3),
+            "Expected width 8 for port 'd', got width 7",
+        )],
+    );
+
+    assert_eq!(
+        root.search_reference(code.source(), code.s("width - 1", 1).start()),
+        Some(code.s1("width").pos())
+    );
+}
+
+#[test]
+fn error_on_range_bounds_of_different_or_non_discrete_types() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant flag : boolean := false;
+  subtype mixed_t is integer range 0 to flag;
+  subtype real_t is real range 0.0 to 1.0;
+end package;
+
+package body pkg is
+  procedure proc is
+  begin
+    for i in 0.0 to 1.0 loop
+    end loop;
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("0 to flag"),
+                "Range bounds must have the same type, got universal_integer and type 'BOOLEAN'",
+            ),
+            Diagnostic::error(
+                code.s("0.0", 2),
+                "Expected discrete type for bound of discrete range, got universal_real",
+            ),
+        ],
+    );
+}
@@ -4,7 +4,7 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
+use crate::analysis::{DesignRoot, Lint};
 use crate::data::*;
 use crate::syntax::test::*;
 use crate::syntax::Symbols;
@@ -15,6 +15,7 @@ use std::sync::Arc;
 pub struct LibraryBuilder {
     code_builder: CodeBuilder,
     libraries: HashMap<Symbol, Vec<Code>>,
+    lints: Vec<Lint>,
 }
 
 impl LibraryBuilder {
@@ -22,6 +23,7 @@ impl LibraryBuilder {
         LibraryBuilder {
             code_builder: CodeBuilder::new(),
             libraries: HashMap::default(),
+            lints: Vec::new(),
         }
     }
 
@@ -43,9 +45,16 @@ impl LibraryBuilder {
         code
     }
 
-    pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
+    pub fn enable_lint(&mut self, lint: Lint) {
+        self.lints.push(lint);
+    }
+
+    pub fn get_unanalyzed_root(&self) -> DesignRoot {
         let mut root = DesignRoot::new(self.code_builder.symbols.clone());
-        let mut diagnostics = Vec::new();
+
+        for lint in self.lints.iter() {
+            root.enable_lint(lint.clone());
+        }
 
         add_standard_library(self.symbols(), &mut root);
 
@@ -55,8 +64,13 @@ impl LibraryBuilder {
             }
         }
 
-        root.analyze(&mut diagnostics);
+        root
+    }
 
+    pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
+        let mut root = self.get_unanalyzed_root();
+        let mut diagnostics = Vec::new();
+        root.analyze(&mut diagnostics);
         (root, diagnostics)
     }
 
@@ -473,6 +473,48 @@ end package;
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn string_literal_resolves_without_use_clause() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant s : string := \"hello\";
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let string = root
+        .search_reference(code.source(), code.s1("string").start())
+        .unwrap();
+    assert!(string.source.file_name().ends_with("standard.vhd"));
+}
+
+#[test]
+fn character_literal_resolves_without_use_clause() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : character := 'a';
+end package;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let character = root
+        .search_reference(code.source(), code.s1("character").start())
+        .unwrap();
+    assert!(character.source.file_name().ends_with("standard.vhd"));
+}
+
 /// This was a bug during development
 #[test]
 fn duplicate_explicit_library_is_not_duplicate() {
@@ -615,3 +657,35 @@ end package;
         Some(deepest_pos.pos())
     );
 }
+
+#[test]
+fn use_clause_of_overloaded_name_makes_all_overloads_visible() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function f(arg : natural) return natural;
+  function f(arg : character) return natural;
+  function other return natural;
+end package;
+
+use work.pkg.f;
+
+package user is
+  constant c0 : natural := f(0);
+  constant c1 : natural := f('a');
+  constant c2 : natural := other;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("other", 2),
+            "No declaration of 'other'",
+        )],
+    );
+}
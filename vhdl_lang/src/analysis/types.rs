@@ -0,0 +1,1461 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
+
+//! Best effort type checking
+//!
+//! Without full overload resolution the type of an expression is only known in some cases
+//! Checks are only performed when the type is known to avoid false errors
+
+use super::analyze::*;
+use super::region::*;
+use super::semantic::ResolvedName;
+use crate::ast::*;
+use crate::data::*;
+use std::sync::Arc;
+
+/// The type of an expression which could be determined without knowing the context
+#[derive(Clone)]
+pub enum ExpressionType {
+    Known(Arc<NamedEntity>),
+    UniversalInteger,
+    UniversalReal,
+}
+
+/// The type of a positional argument of a call
+/// A string literal gets its type from the parameter it is associated with
+enum ArgumentType<'a> {
+    Expression(ExpressionType),
+    StringLiteral(&'a Latin1String),
+}
+
+impl<'a> ArgumentType<'a> {
+    fn is_compatible_with(&self, param_type: &NamedEntity) -> bool {
+        match self {
+            ArgumentType::Expression(typ) => typ.is_compatible_with(param_type),
+            ArgumentType::StringLiteral(value) => is_string_literal_type(param_type, value),
+        }
+    }
+}
+
+/// The type of an expression as presented to users of the library
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct TypeInfo {
+    /// The designator of the base type, or universal_integer/universal_real
+    pub name: String,
+    /// The declaration of the base type, None for universal types
+    pub decl_pos: Option<SrcPos>,
+}
+
+impl ExpressionType {
+    pub fn type_info(&self) -> TypeInfo {
+        match self {
+            ExpressionType::Known(ent) => TypeInfo {
+                name: ent.base_type().designator().to_string(),
+                decl_pos: ent.base_type().decl_pos().cloned(),
+            },
+            ExpressionType::UniversalInteger => TypeInfo {
+                name: "universal_integer".to_owned(),
+                decl_pos: None,
+            },
+            ExpressionType::UniversalReal => TypeInfo {
+                name: "universal_real".to_owned(),
+                decl_pos: None,
+            },
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            ExpressionType::Known(ent) => format!("type '{}'", ent.base_type().designator()),
+            ExpressionType::UniversalInteger => "universal_integer".to_owned(),
+            ExpressionType::UniversalReal => "universal_real".to_owned(),
+        }
+    }
+
+    /// True if an expression of this type may be used where the type is expected
+    pub fn is_compatible_with(&self, type_ent: &NamedEntity) -> bool {
+        match self {
+            ExpressionType::Known(ent) => ent.base_type().id() == type_ent.base_type().id(),
+            ExpressionType::UniversalInteger => {
+                if let Some(Type::Integer(..)) = type_ent.base_type_class() {
+                    true
+                } else {
+                    false
+                }
+            }
+            ExpressionType::UniversalReal => {
+                if let Some(Type::Real) = type_ent.base_type_class() {
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn class(&self) -> Option<&Type> {
+        match self {
+            ExpressionType::Known(ent) => ent.base_type_class(),
+            ExpressionType::UniversalInteger => Some(&Type::Integer(None)),
+            ExpressionType::UniversalReal => Some(&Type::Real),
+        }
+    }
+
+    fn is_universal(&self) -> bool {
+        match self {
+            ExpressionType::Known(..) => false,
+            ExpressionType::UniversalInteger | ExpressionType::UniversalReal => true,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        match self.class() {
+            Some(Type::Integer(..)) | Some(Type::Real) => true,
+            _ => false,
+        }
+    }
+
+    fn is_physical(&self) -> bool {
+        if let Some(Type::Physical) = self.class() {
+            true
+        } else {
+            false
+        }
+    }
+
+    fn element_type(&self) -> Option<ExpressionType> {
+        if let Some(Type::Array(Some(elem))) = self.class() {
+            Some(ExpressionType::Known(elem.base().clone()))
+        } else {
+            None
+        }
+    }
+
+    fn is_same_as(&self, other: &ExpressionType) -> bool {
+        match (self, other) {
+            (ExpressionType::Known(ent), ExpressionType::Known(other)) => {
+                ent.base_type().id() == other.base_type().id()
+            }
+            (ExpressionType::UniversalInteger, ExpressionType::UniversalInteger) => true,
+            (ExpressionType::UniversalReal, ExpressionType::UniversalReal) => true,
+            _ => false,
+        }
+    }
+
+    /// The type of the result when combining two numeric operands of the same type
+    /// where one operand may be of universal type
+    fn common_type(&self, other: &ExpressionType) -> Option<ExpressionType> {
+        if self.is_same_as(other) {
+            Some(self.clone())
+        } else if let ExpressionType::Known(ref ent) = self {
+            if other.is_universal() && other.is_compatible_with(ent) {
+                Some(self.clone())
+            } else {
+                None
+            }
+        } else if let ExpressionType::Known(ref ent) = other {
+            if self.is_universal() && self.is_compatible_with(ent) {
+                Some(other.clone())
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Evaluate a time literal with a unit of the standard time type in femtoseconds
+fn static_time(expr: &Expression) -> Option<f64> {
+    if let Expression::Literal(Literal::Physical(ref value, ref unit)) = expr {
+        let scale = match unit.name_utf8().to_lowercase().as_str() {
+            "fs" => 1e0,
+            "ps" => 1e3,
+            "ns" => 1e6,
+            "us" => 1e9,
+            "ms" => 1e12,
+            "sec" => 1e15,
+            "min" => 60e15,
+            "hr" => 3600e15,
+            _ => return None,
+        };
+        let value = match value {
+            AbstractLiteral::Integer(value) => *value as f64,
+            AbstractLiteral::Real(value) => *value,
+        };
+        Some(value * scale)
+    } else {
+        None
+    }
+}
+
+/// True if a string literal may be of the type, that is when the type is an array type whose
+/// element type is an enumeration type with the characters of the literal as literals
+pub(super) fn is_string_literal_type(type_ent: &NamedEntity, value: &Latin1String) -> bool {
+    let elem = match type_ent.base_type_class() {
+        Some(Type::Array(Some(elem))) => elem.base().base_type(),
+        // The element type could not be resolved
+        Some(Type::Array(None)) => return true,
+        _ => return false,
+    };
+
+    match elem.kind() {
+        NamedEntityKind::TypeDeclaration(Type::Enum, ..) => {}
+        _ => return false,
+    }
+
+    let literals = elem.kind().implicit_declarations();
+    value.bytes.iter().all(|chr| {
+        literals.iter().any(|literal| {
+            if let NamedEntityKind::EnumLiteral(..) = literal.kind() {
+                literal.designator() == &Designator::Character(*chr)
+            } else {
+                false
+            }
+        })
+    })
+}
+
+/// The values of an item of the right hand side of an assignment
+pub trait AssignedValues {
+    fn values(&self) -> Vec<&WithPos<Expression>>;
+}
+
+impl AssignedValues for WithPos<Expression> {
+    fn values(&self) -> Vec<&WithPos<Expression>> {
+        vec![self]
+    }
+}
+
+impl AssignedValues for Waveform {
+    fn values(&self) -> Vec<&WithPos<Expression>> {
+        if let Waveform::Elements(ref elems) = self {
+            elems.iter().map(|elem| &elem.value).collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The simple name of a declaration as returned by the 'simple_name attribute
+/// Basic identifiers are in lower case while extended identifiers keep their case
+fn simple_name_string(designator: &Designator) -> String {
+    match designator {
+        Designator::Identifier(sym) => {
+            let name = sym.name_utf8();
+            if name.starts_with('\\') {
+                name
+            } else {
+                name.to_lowercase()
+            }
+        }
+        Designator::Character(chr) => format!("'{}'", Latin1String::new(&[*chr])),
+        Designator::OperatorSymbol(op) => format!("\"{}\"", op),
+    }
+}
+
+fn binary_operator_symbol(op: Binary) -> &'static str {
+    match op {
+        Binary::And => "and",
+        Binary::Or => "or",
+        Binary::Nand => "nand",
+        Binary::Nor => "nor",
+        Binary::Xor => "xor",
+        Binary::Xnor => "xnor",
+        Binary::EQ => "=",
+        Binary::NE => "/=",
+        Binary::LT => "<",
+        Binary::LTE => "<=",
+        Binary::GT => ">",
+        Binary::GTE => ">=",
+        Binary::QueEQ => "?=",
+        Binary::QueNE => "?/=",
+        Binary::QueLT => "?<",
+        Binary::QueLTE => "?<=",
+        Binary::QueGT => "?>",
+        Binary::QueGTE => "?>=",
+        Binary::SLL => "sll",
+        Binary::SRL => "srl",
+        Binary::SLA => "sla",
+        Binary::SRA => "sra",
+        Binary::ROL => "rol",
+        Binary::ROR => "ror",
+        Binary::Plus => "+",
+        Binary::Minus => "-",
+        Binary::Concat => "&",
+        Binary::Times => "*",
+        Binary::Div => "/",
+        Binary::Mod => "mod",
+        Binary::Rem => "rem",
+        Binary::Pow => "**",
+    }
+}
+
+fn unary_operator_symbol(op: Unary) -> &'static str {
+    match op {
+        Unary::And => "and",
+        Unary::Or => "or",
+        Unary::Nand => "nand",
+        Unary::Nor => "nor",
+        Unary::Xor => "xor",
+        Unary::Xnor => "xnor",
+        Unary::Abs => "abs",
+        Unary::Not => "not",
+        Unary::Minus => "-",
+        Unary::Plus => "+",
+        Unary::QueQue => "??",
+    }
+}
+
+/// The type of an operator call among the declarations of the operator
+/// Returns None if there are no matching declarations
+fn matching_operator_type(
+    entities: NamedEntities,
+    operands: &[Option<ExpressionType>],
+) -> Option<Option<ExpressionType>> {
+    let candidates: Vec<_> = match entities {
+        NamedEntities::Single(ent) => vec![ent],
+        NamedEntities::Overloaded(overloaded) => overloaded.entities().cloned().collect(),
+    };
+
+    let candidates: Vec<_> = candidates
+        .into_iter()
+        .filter(|ent| {
+            if let Some(signature) = ent.signature() {
+                let param_types = signature.param_types();
+                param_types.len() == operands.len()
+                    && param_types
+                        .iter()
+                        .zip(operands.iter())
+                        .all(|(param_type, operand)| {
+                            operand
+                                .as_ref()
+                                .map(|operand| operand.is_compatible_with(param_type))
+                                .unwrap_or(true)
+                        })
+            } else {
+                false
+            }
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        None
+    } else {
+        Some(unique_return_type(candidates.iter()))
+    }
+}
+
+fn operator_designator(symbol: &str) -> Designator {
+    Designator::OperatorSymbol(Latin1String::new(symbol.as_bytes()))
+}
+
+/// The unique return type of the overloaded entities or None if ambiguous
+fn unique_return_type<'e>(
+    candidates: impl Iterator<Item = &'e Arc<NamedEntity>>,
+) -> Option<ExpressionType> {
+    let mut result: Option<&Arc<NamedEntity>> = None;
+    for ent in candidates {
+        let return_type = ent.signature()?.return_type()?;
+        if let Some(previous) = result {
+            if previous.base_type().id() != return_type.base_type().id() {
+                return None;
+            }
+        } else {
+            result = Some(return_type);
+        }
+    }
+    result.map(|ent| ExpressionType::Known(ent.clone()))
+}
+
+impl<'a> AnalyzeContext<'a> {
+    /// Lookup a type declared in the standard package
+    /// Returns None when analyzing the standard package itself or when there is no standard library
+    pub fn standard_type(&self, name: &str) -> Option<Arc<NamedEntity>> {
+        if self.is_standard_package() {
+            return None;
+        }
+
+        let standard = self.standard_package_analysis()?.ok()?;
+        standard
+            .result()
+            .region
+            .lookup_immediate(&self.symbol_utf8(name).into())?
+            .as_non_overloaded()
+            .cloned()
+    }
+
+    /// Lookup what an already analyzed name refers to without adding any diagnostics
+    pub fn lookup_name(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        name: &Name,
+    ) -> Option<NamedEntities> {
+        match name {
+            Name::Designator(designator) => region.lookup_within(pos, designator.designator()).ok(),
+            Name::Selected(prefix, suffix) => {
+                let prefix_ent = self
+                    .lookup_name(region, &prefix.pos, &prefix.item)?
+                    .into_non_overloaded()
+                    .ok()?;
+                self.lookup_selected(&prefix.pos, &prefix_ent, suffix)
+                    .ok()?
+                    .into_known()
+            }
+            _ => None,
+        }
+    }
+
+    /// The type of a named entity when used as a value
+    fn entity_type(&self, ent: &NamedEntity) -> Option<ExpressionType> {
+        match ent.actual_kind() {
+            NamedEntityKind::Object(_, Some(ref subtype), _) => {
+                Some(ExpressionType::Known(subtype.base().clone()))
+            }
+            NamedEntityKind::InterfaceObject(ref object) => Some(ExpressionType::Known(
+                object.subtype.as_ref()?.base().clone(),
+            )),
+            NamedEntityKind::PhysicalLiteral(ref type_ent) => {
+                Some(ExpressionType::Known(type_ent.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    fn named_entities_type(&self, entities: &NamedEntities) -> Option<ExpressionType> {
+        match entities {
+            NamedEntities::Single(ent) => self.entity_type(ent),
+            NamedEntities::Overloaded(overloaded) => unique_return_type(overloaded.entities()),
+        }
+    }
+
+    fn name_type(&self, region: &Region<'_>, pos: &SrcPos, name: &Name) -> Option<ExpressionType> {
+        match name {
+            Name::Designator(..) | Name::Selected(..) => {
+                let entities = self.lookup_name(region, pos, name)?;
+                self.named_entities_type(&entities)
+            }
+            Name::FunctionCall(ref fcall) => {
+                let entities = self.lookup_name(region, &fcall.name.pos, &fcall.name.item)?;
+                match entities {
+                    // Indexed name of an array object
+                    NamedEntities::Single(ref ent) => self.entity_type(ent)?.element_type(),
+                    NamedEntities::Overloaded(ref overloaded) => {
+                        let arity = fcall.parameters.len();
+                        unique_return_type(overloaded.entities().filter(|ent| {
+                            ent.signature()
+                                .map(|signature| signature.param_types().len() >= arity)
+                                .unwrap_or(false)
+                        }))
+                    }
+                }
+            }
+            Name::Indexed(ref prefix, ..) => self
+                .name_type(region, &prefix.pos, &prefix.item)?
+                .element_type(),
+            Name::Slice(ref prefix, ..) => self.name_type(region, &prefix.pos, &prefix.item),
+            Name::Attribute(ref attr) => self.attribute_type(attr),
+            Name::SelectedAll(..) | Name::External(..) => None,
+        }
+    }
+
+    fn attribute_type(&self, attr: &AttributeName) -> Option<ExpressionType> {
+        let name = attr.attr.item.name_utf8().to_lowercase();
+        match name.as_str() {
+            "event" | "active" | "stable" | "quiet" => {
+                self.standard_type("BOOLEAN").map(ExpressionType::Known)
+            }
+            "last_event" | "last_active" => self.standard_type("TIME").map(ExpressionType::Known),
+            "image" | "simple_name" | "instance_name" | "path_name" => {
+                self.standard_type("STRING").map(ExpressionType::Known)
+            }
+            "length" => Some(ExpressionType::UniversalInteger),
+            _ => None,
+        }
+    }
+
+    /// The value of an analyzed 'simple_name, 'path_name or 'instance_name attribute when it
+    /// is known without elaboration
+    /// The path of items other than the design units themselves depends on the design hierarchy
+    /// so 'path_name and 'instance_name are only evaluated for entities and packages
+    pub fn static_name_attribute(
+        &self,
+        region: &Region<'_>,
+        attr: &AttributeName,
+    ) -> Option<String> {
+        let ent = self
+            .lookup_name(region, &attr.name.pos, &attr.name.item)?
+            .into_non_overloaded()
+            .ok()?;
+        let simple_name = simple_name_string(ent.designator());
+
+        let name = attr.attr.item.name_utf8().to_lowercase();
+        if name == "simple_name" {
+            return Some(simple_name);
+        } else if name != "path_name" && name != "instance_name" {
+            return None;
+        }
+
+        // The entity or package of the current design unit
+        let current = self.current_unit_id();
+        let (primary_kind, primary_pos) = self.current_primary_unit()?;
+        if ent.decl_pos() != Some(primary_pos) {
+            return None;
+        }
+
+        match primary_kind {
+            AnyKind::Primary(PrimaryKind::Entity) => {
+                if name == "path_name" {
+                    Some(format!(":{}:", simple_name))
+                } else if let UnitKey::Secondary(_, ref arch_name) = current.key() {
+                    Some(format!(
+                        ":{}({}):",
+                        simple_name,
+                        simple_name_string(&Designator::Identifier(arch_name.clone()))
+                    ))
+                } else {
+                    // The architecture is chosen during elaboration
+                    None
+                }
+            }
+            AnyKind::Primary(PrimaryKind::Package) => Some(format!(
+                ":{}:{}:",
+                simple_name_string(&Designator::Identifier(current.library_name().clone())),
+                simple_name
+            )),
+            _ => None,
+        }
+    }
+
+    /// Check that an object prefix of an indexed or sliced name is of an array type
+    /// The value of an access type is implicitly dereferenced so only prefixes of scalar or
+    /// record types are known to be errors
+    pub fn check_array_prefix(
+        &self,
+        region: &Region<'_>,
+        prefix: &WithPos<Name>,
+        operation: &str,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match prefix.item {
+            Name::Designator(..) | Name::Selected(..) => {}
+            _ => return,
+        }
+
+        let ent = if let Some(ent) = self
+            .lookup_name(region, &prefix.pos, &prefix.item)
+            .and_then(|entities| entities.into_non_overloaded().ok())
+        {
+            ent
+        } else {
+            return;
+        };
+
+        let type_ent = if let Some(subtype) = ent.object_subtype() {
+            subtype.base().base_type()
+        } else {
+            return;
+        };
+
+        let is_scalar_or_record = match type_ent.base_type_class() {
+            Some(Type::Enum)
+            | Some(Type::Integer(..))
+            | Some(Type::Real)
+            | Some(Type::Physical)
+            | Some(Type::Record(..)) => true,
+            _ => false,
+        };
+        if is_scalar_or_record {
+            diagnostics.push(Diagnostic::error(
+                &prefix.pos,
+                format!(
+                    "Cannot {} {} of non-array type '{}'",
+                    operation,
+                    ent.describe(),
+                    type_ent.designator()
+                ),
+            ));
+        }
+    }
+
+    /// Find the type of an operator call among the visible explicit declarations of the operator
+    /// Returns None if there are no matching declarations
+    fn explicit_operator_type(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        symbol: &str,
+        operands: &[Option<ExpressionType>],
+    ) -> Option<Option<ExpressionType>> {
+        let entities = region
+            .lookup_within(pos, &operator_designator(symbol))
+            .ok()?;
+        matching_operator_type(entities, operands)
+    }
+
+    /// The result type of a predefined binary operator when both operand types are known
+    fn predefined_binary_type(
+        &self,
+        op: Binary,
+        left: Option<ExpressionType>,
+        right: Option<ExpressionType>,
+    ) -> Option<ExpressionType> {
+        match op {
+            Binary::EQ | Binary::NE | Binary::LT | Binary::LTE | Binary::GT | Binary::GTE => {
+                self.standard_type("BOOLEAN").map(ExpressionType::Known)
+            }
+            Binary::QueEQ
+            | Binary::QueNE
+            | Binary::QueLT
+            | Binary::QueLTE
+            | Binary::QueGT
+            | Binary::QueGTE => {
+                let (left, right) = (left?, right?);
+                if self.is_predefined_matching_operation(op, &left, &right) {
+                    // The result is the scalar type of the operands or their element type
+                    left.element_type().or(Some(left))
+                } else {
+                    None
+                }
+            }
+            Binary::And
+            | Binary::Or
+            | Binary::Nand
+            | Binary::Nor
+            | Binary::Xor
+            | Binary::Xnor
+            | Binary::Plus
+            | Binary::Minus
+            | Binary::Mod
+            | Binary::Rem => left?.common_type(&right?),
+            Binary::Times => {
+                let (left, right) = (left?, right?);
+                if left.is_physical() && right.is_numeric() {
+                    Some(left)
+                } else if left.is_numeric() && right.is_physical() {
+                    Some(right)
+                } else {
+                    left.common_type(&right)
+                }
+            }
+            Binary::Div => {
+                let (left, right) = (left?, right?);
+                if left.is_physical() && right.is_numeric() {
+                    Some(left)
+                } else if left.is_physical() && right.is_physical() {
+                    Some(ExpressionType::UniversalInteger)
+                } else {
+                    left.common_type(&right)
+                }
+            }
+            Binary::Pow
+            | Binary::SLL
+            | Binary::SRL
+            | Binary::SLA
+            | Binary::SRA
+            | Binary::ROL
+            | Binary::ROR => left,
+            Binary::Concat => {
+                // An element may be of universal type such as an integer literal
+                let (left, right) = (left?, right?);
+                let left_elem = left.element_type();
+                if left_elem.is_some() && left.is_same_as(&right)
+                    || left_elem
+                        .map(|elem| elem.common_type(&right).is_some())
+                        .unwrap_or(false)
+                {
+                    Some(left)
+                } else if right
+                    .element_type()
+                    .map(|elem| elem.common_type(&left).is_some())
+                    .unwrap_or(false)
+                {
+                    Some(right)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// True if the type is BIT or STD_ULOGIC for which the condition and matching operators
+    /// are predefined
+    /// STD_ULOGIC is recognized by name since the ieee library may not be analyzed
+    fn is_bit_or_std_ulogic(&self, typ: &ExpressionType) -> bool {
+        if let Some(bit) = self.standard_type("BIT") {
+            if typ.is_compatible_with(&bit) {
+                return true;
+            }
+        }
+
+        if let ExpressionType::Known(ref ent) = typ {
+            if let Some(Type::Enum) = ent.base_type_class() {
+                ent.base_type().designator()
+                    == &Designator::Identifier(self.symbol_utf8("STD_ULOGIC"))
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    /// True if the predefined matching operator applies to the operands
+    /// The matching relational operators are predefined for BIT and STD_ULOGIC and the
+    /// matching equality operators also for one-dimensional arrays of those
+    fn is_predefined_matching_operation(
+        &self,
+        op: Binary,
+        left: &ExpressionType,
+        right: &ExpressionType,
+    ) -> bool {
+        if self.is_bit_or_std_ulogic(left) && left.is_same_as(right) {
+            return true;
+        }
+
+        match (op, left.element_type()) {
+            (Binary::QueEQ, Some(elem)) | (Binary::QueNE, Some(elem)) => {
+                self.is_bit_or_std_ulogic(&elem) && left.is_same_as(right)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check that an analyzed condition, matching or concatenation operator has a declaration
+    /// for the operand types when they are known
+    pub fn check_operator_operands(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        expr: &Expression,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let (symbol, operands) = match expr {
+            Expression::Unary(Unary::QueQue, ref inner) => {
+                (unary_operator_symbol(Unary::QueQue), vec![inner])
+            }
+            Expression::Binary(op, ref left, ref right) => match op {
+                Binary::QueEQ
+                | Binary::QueNE
+                | Binary::QueLT
+                | Binary::QueLTE
+                | Binary::QueGT
+                | Binary::QueGTE
+                | Binary::Concat => (binary_operator_symbol(*op), vec![left, right]),
+                _ => {
+                    return;
+                }
+            },
+            _ => {
+                return;
+            }
+        };
+
+        let mut types = Vec::with_capacity(operands.len());
+        for operand in operands.iter() {
+            if let Some(typ) = self.expression_type(region, operand) {
+                types.push(typ);
+            } else {
+                return;
+            }
+        }
+
+        if let Expression::Binary(Binary::Concat, ..) = expr {
+            // Two elements are concatenated into an array type known only from the context
+            if types.iter().all(|typ| typ.element_type().is_none()) {
+                return;
+            }
+        }
+
+        let is_predefined = match expr {
+            Expression::Binary(Binary::Concat, ..) => self
+                .predefined_binary_type(
+                    Binary::Concat,
+                    Some(types[0].clone()),
+                    Some(types[1].clone()),
+                )
+                .is_some(),
+            Expression::Binary(op, ..) => {
+                self.is_predefined_matching_operation(*op, &types[0], &types[1])
+            }
+            _ => self.is_bit_or_std_ulogic(&types[0]),
+        };
+
+        if is_predefined {
+            return;
+        }
+
+        let operand_types: Vec<_> = types.iter().cloned().map(Some).collect();
+        if self
+            .explicit_operator_type(region, pos, symbol, &operand_types)
+            .is_some()
+        {
+            return;
+        }
+
+        let described: Vec<_> = types.iter().map(|typ| typ.describe()).collect();
+        diagnostics.push(Diagnostic::error(
+            pos,
+            format!(
+                "No declaration of operator \"{}\" for {}",
+                symbol,
+                described.join(" and ")
+            ),
+        ));
+    }
+
+    /// The type of an analyzed expression or None if it cannot be determined
+    pub fn expression_type(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+    ) -> Option<ExpressionType> {
+        self.expression_pos_type(region, &expr.pos, &expr.item)
+    }
+
+    /// The type of an analyzed expression at a position or None if it cannot be determined
+    pub fn expression_pos_type(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        expr: &Expression,
+    ) -> Option<ExpressionType> {
+        match *expr {
+            Expression::Literal(ref literal) => match literal {
+                Literal::AbstractLiteral(AbstractLiteral::Integer(..)) => {
+                    Some(ExpressionType::UniversalInteger)
+                }
+                Literal::AbstractLiteral(AbstractLiteral::Real(..)) => {
+                    Some(ExpressionType::UniversalReal)
+                }
+                Literal::Physical(_, ref unit) => {
+                    let entities = region
+                        .lookup_within(pos, &Designator::Identifier(unit.clone()))
+                        .ok()?;
+                    self.named_entities_type(&entities)
+                }
+                Literal::Character(chr) => {
+                    let entities = region
+                        .lookup_within(pos, &Designator::Character(*chr))
+                        .ok()?;
+                    self.named_entities_type(&entities)
+                }
+                Literal::String(..) | Literal::BitString(..) | Literal::Null => None,
+            },
+            Expression::Name(ref name) => self.name_type(region, pos, name),
+            Expression::Qualified(ref qexpr) => {
+                let ent = self
+                    .lookup_name(region, &qexpr.name.pos, &qexpr.name.item)?
+                    .into_non_overloaded()
+                    .ok()?;
+                if ent.actual_kind().is_type() {
+                    Some(ExpressionType::Known(ent))
+                } else {
+                    None
+                }
+            }
+            Expression::Unary(op, ref inner) => {
+                let operand = self.expression_type(region, inner);
+                if let Some(typ) = self.explicit_operator_type(
+                    region,
+                    pos,
+                    unary_operator_symbol(op),
+                    std::slice::from_ref(&operand),
+                ) {
+                    return typ;
+                }
+
+                match op {
+                    Unary::QueQue => self.standard_type("BOOLEAN").map(ExpressionType::Known),
+                    Unary::Not | Unary::Abs | Unary::Minus | Unary::Plus => operand,
+                    // Reduction operators return the element type
+                    Unary::And
+                    | Unary::Or
+                    | Unary::Nand
+                    | Unary::Nor
+                    | Unary::Xor
+                    | Unary::Xnor => operand?.element_type(),
+                }
+            }
+            Expression::Binary(op, ref left, ref right) => {
+                let left = self.expression_type(region, left);
+                let right = self.expression_type(region, right);
+                if let Some(typ) = self.explicit_operator_type(
+                    region,
+                    pos,
+                    binary_operator_symbol(op),
+                    &[left.clone(), right.clone()],
+                ) {
+                    return typ;
+                }
+                self.predefined_binary_type(op, left, right)
+            }
+            Expression::Conditional(ref conditionals) => {
+                // The type of the first alternative, the others are not compared
+                let first = conditionals.conditionals.first()?;
+                self.expression_type(region, &first.item)
+            }
+            Expression::Aggregate(..) | Expression::New(..) => None,
+        }
+    }
+
+    /// The overloaded subprograms which can be called with the positional arguments
+    /// Returns None when there are named or open arguments or when the type of an argument
+    /// cannot be determined
+    pub fn matching_subprograms(
+        &self,
+        region: &Region<'_>,
+        overloaded: &OverloadedName,
+        args: &[AssociationElement],
+    ) -> Option<Vec<Arc<NamedEntity>>> {
+        let mut arg_types = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            if arg.formal.is_some() {
+                return None;
+            }
+            match arg.actual.item {
+                ActualPart::Expression(Expression::Literal(Literal::String(ref value))) => {
+                    arg_types.push(ArgumentType::StringLiteral(value));
+                }
+                ActualPart::Expression(ref expr) => {
+                    arg_types.push(ArgumentType::Expression(self.expression_pos_type(
+                        region,
+                        &arg.actual.pos,
+                        expr,
+                    )?));
+                }
+                ActualPart::Open => {
+                    return None;
+                }
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for ent in overloaded.entities() {
+            let signature = match ent.actual_kind() {
+                NamedEntityKind::Subprogram(signature)
+                | NamedEntityKind::SubprogramDecl(signature) => signature,
+                // Enumeration literals are not called with arguments
+                _ => {
+                    return None;
+                }
+            };
+
+            if signature.can_be_called_with_arity(arg_types.len())
+                && signature
+                    .param_types()
+                    .iter()
+                    .zip(arg_types.iter())
+                    .all(|(param_type, arg_type)| arg_type.is_compatible_with(param_type))
+            {
+                candidates.push(ent.clone());
+            }
+        }
+        Some(candidates)
+    }
+
+    /// Describe the types of the positional arguments of a call
+    pub fn describe_argument_types(
+        &self,
+        region: &Region<'_>,
+        args: &[AssociationElement],
+    ) -> String {
+        let described: Vec<_> = args
+            .iter()
+            .filter_map(|arg| match arg.actual.item {
+                ActualPart::Expression(Expression::Literal(Literal::String(..))) => {
+                    Some("string literal".to_owned())
+                }
+                ActualPart::Expression(ref expr) => self
+                    .expression_pos_type(region, &arg.actual.pos, expr)
+                    .map(|typ| typ.describe()),
+                ActualPart::Open => None,
+            })
+            .collect();
+        described.join(" and ")
+    }
+
+    /// The statically known number of elements of an analyzed array expression
+    /// Known for objects with a static length, string literals and concatenations of those
+    pub fn expression_length(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        expr: &Expression,
+    ) -> Option<u64> {
+        match expr {
+            Expression::Name(ref name) => {
+                let ent = self
+                    .lookup_name(region, pos, name)?
+                    .into_non_overloaded()
+                    .ok()?;
+                ent.object_subtype()?.length()
+            }
+            Expression::Literal(Literal::String(ref value)) => Some(value.len() as u64),
+            Expression::Binary(Binary::Concat, ref left, ref right) => {
+                let left_type = self.expression_type(region, left)?;
+                let right_type = self.expression_type(region, right)?;
+                let result = self.predefined_binary_type(
+                    Binary::Concat,
+                    Some(left_type.clone()),
+                    Some(right_type.clone()),
+                )?;
+
+                // An operand which is not of the array type of the result is a single element
+                let operand_length = |operand: &WithPos<Expression>, typ: &ExpressionType| {
+                    if typ.is_same_as(&result) {
+                        self.expression_length(region, &operand.pos, &operand.item)
+                    } else {
+                        Some(1)
+                    }
+                };
+                Some(operand_length(left, &left_type)? + operand_length(right, &right_type)?)
+            }
+            _ => None,
+        }
+    }
+
+    /// The direction of the static range of an object denoted by an analyzed name
+    pub fn name_direction(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        name: &Name,
+    ) -> Option<Direction> {
+        let ent = self
+            .lookup_name(region, pos, name)?
+            .into_non_overloaded()
+            .ok()?;
+        ent.object_subtype()?.direction()
+    }
+
+    /// Check that an analyzed expression has the expected type when the type can be determined
+    /// A string literal has the expected type when its characters are literals of the element type
+    pub fn check_expression_type(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+        expected: &NamedEntity,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        self.check_expression_pos_type(region, &expr.pos, &expr.item, expected, diagnostics);
+    }
+
+    /// Check that an analyzed expression at a position has the expected type when the type
+    /// can be determined
+    pub fn check_expression_pos_type(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        expr: &Expression,
+        expected: &NamedEntity,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Expression::Literal(Literal::String(ref value)) = expr {
+            if !is_string_literal_type(expected, value) {
+                diagnostics.push(Diagnostic::error(
+                    pos,
+                    format!(
+                        "Expected type '{}', got string literal",
+                        expected.designator()
+                    ),
+                ));
+            }
+        } else if let Some(actual) = self.expression_pos_type(region, pos, expr) {
+            if !actual.is_compatible_with(expected) {
+                diagnostics.push(Diagnostic::error(
+                    pos,
+                    format!(
+                        "Expected type '{}', got {}",
+                        expected.designator(),
+                        actual.describe()
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// The type of an analyzed range bound when the types of all operands are known
+    /// The type of an operator with operands of unknown types or of a function call is only
+    /// a guess among the visible declarations of the operator or function
+    fn range_bound_type(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+    ) -> Option<ExpressionType> {
+        let has_known_operands = match expr.item {
+            Expression::Unary(_, ref inner) => self.range_bound_type(region, inner).is_some(),
+            Expression::Binary(_, ref left, ref right) => {
+                self.range_bound_type(region, left).is_some()
+                    && self.range_bound_type(region, right).is_some()
+            }
+            Expression::Name(ref name) => match name.as_ref() {
+                Name::FunctionCall(..) => false,
+                _ => true,
+            },
+            Expression::Literal(..) | Expression::Qualified(..) => true,
+            _ => false,
+        };
+
+        if has_known_operands {
+            self.expression_type(region, expr)
+        } else {
+            None
+        }
+    }
+
+    /// Check that the bounds of an analyzed range have the same type when the types can be
+    /// determined, universal bounds may be used for any type of the same class
+    pub fn check_range_bound_types(
+        &self,
+        region: &Region<'_>,
+        range: &RangeConstraint,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let left = self.range_bound_type(region, &range.left_expr);
+        let right = self.range_bound_type(region, &range.right_expr);
+        let (left, right) = if let (Some(left), Some(right)) = (left, right) {
+            (left, right)
+        } else {
+            return;
+        };
+
+        let is_compatible = match (&left, &right) {
+            (ExpressionType::Known(ref ent), other) | (other, ExpressionType::Known(ref ent)) => {
+                other.is_compatible_with(ent)
+            }
+            (ExpressionType::UniversalInteger, ExpressionType::UniversalInteger)
+            | (ExpressionType::UniversalReal, ExpressionType::UniversalReal) => true,
+            _ => false,
+        };
+
+        if !is_compatible {
+            diagnostics.push(Diagnostic::error(
+                range.left_expr.pos.combine(&range.right_expr.pos),
+                format!(
+                    "Range bounds must have the same type, got {} and {}",
+                    left.describe(),
+                    right.describe()
+                ),
+            ));
+        }
+    }
+
+    /// Check that the bounds of an analyzed discrete range are of a discrete type when the
+    /// types can be determined
+    pub fn check_discrete_range_bounds(
+        &self,
+        region: &Region<'_>,
+        range: &RangeConstraint,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for bound in [&range.left_expr, &range.right_expr].iter() {
+            if let Some(typ) = self.range_bound_type(region, bound) {
+                match typ.class() {
+                    Some(Type::Enum) | Some(Type::Integer(..)) | None => {}
+                    _ => {
+                        diagnostics.push(Diagnostic::error(
+                            &bound.pos,
+                            format!(
+                                "Expected discrete type for bound of discrete range, got {}",
+                                typ.describe()
+                            ),
+                        ));
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check that a value assigned to an analyzed target has the type of the target
+    /// Only simple and selected names of objects are checked
+    pub fn check_target_type(
+        &self,
+        region: &Region<'_>,
+        target: &WithPos<Target>,
+        expr: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let name = if let Target::Name(ref name) = target.item {
+            name
+        } else {
+            return;
+        };
+
+        let ent =
+            if let Some(NamedEntities::Single(ent)) = self.lookup_name(region, &target.pos, name) {
+                ent
+            } else {
+                return;
+            };
+
+        if let Some(subtype) = ent.object_subtype() {
+            self.check_expression_type(region, expr, subtype.base(), diagnostics);
+        }
+    }
+
+    /// Check that string literals assigned to an analyzed target have the type of the target
+    /// Other expressions are only checked for signal force assignments
+    pub fn check_string_literal_target<T: AssignedValues>(
+        &self,
+        region: &Region<'_>,
+        target: &WithPos<Target>,
+        rhs: &AssignmentRightHand<T>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for item in rhs.items() {
+            for expr in item.values() {
+                if let Expression::Literal(Literal::String(..)) = expr.item {
+                    self.check_target_type(region, target, expr, diagnostics);
+                }
+            }
+        }
+    }
+
+    /// Check that an analyzed report expression is of type string
+    /// Operator expressions are not checked since the type of a concatenation of
+    /// string literals depends on overload resolution which is not done yet
+    pub fn check_report(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match expr.item {
+            Expression::Binary(..) | Expression::Unary(..) => return,
+            _ => {}
+        }
+
+        if let Some(string) = self.standard_type("STRING") {
+            self.check_expression_type(region, expr, &string, diagnostics);
+        }
+    }
+
+    /// Check that an analyzed pulse rejection limit is of type time and does not exceed
+    /// the delay of the first element of the waveforms when both are static
+    pub fn check_reject(
+        &self,
+        region: &Region<'_>,
+        reject: &WithPos<Expression>,
+        rhs: &AssignmentRightHand<Waveform>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(time) = self.standard_type("TIME") {
+            self.check_expression_type(region, reject, &time, diagnostics);
+        }
+
+        let limit = if let Some(limit) = static_time(&reject.item) {
+            limit
+        } else {
+            return;
+        };
+
+        for wavf in rhs.items() {
+            let first = if let Waveform::Elements(ref elems) = wavf {
+                if let Some(first) = elems.first() {
+                    first
+                } else {
+                    continue;
+                }
+            } else {
+                continue;
+            };
+
+            // The delay is zero when there is no after clause
+            let delay = if let Some(ref after) = first.after {
+                static_time(&after.item)
+            } else {
+                Some(0.0)
+            };
+
+            if delay.map_or(false, |delay| limit > delay) {
+                let mut error = Diagnostic::error(
+                    &reject.pos,
+                    "Pulse rejection limit must not exceed the delay of the first waveform element",
+                );
+                if let Some(ref after) = first.after {
+                    error.add_related(&after.pos, "Delay of the first waveform element");
+                }
+                diagnostics.push(error);
+            }
+        }
+    }
+
+    /// Check that an analyzed severity expression is of type severity_level
+    pub fn check_severity(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        if let Some(severity_level) = self.standard_type("SEVERITY_LEVEL") {
+            self.check_expression_type(region, expr, &severity_level, diagnostics);
+        }
+    }
+
+    /// Check that an analyzed condition is of type boolean or can be implicitly converted to boolean
+    /// using the condition operator ??
+    pub fn check_condition(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let boolean = if let Some(boolean) = self.standard_type("BOOLEAN") {
+            boolean
+        } else {
+            return;
+        };
+
+        let actual = if let Some(actual) = self.expression_type(region, expr) {
+            actual
+        } else {
+            return;
+        };
+
+        if actual.is_compatible_with(&boolean) {
+            return;
+        }
+
+        if let Some(bit) = self.standard_type("BIT") {
+            if actual.is_compatible_with(&bit) {
+                // Predefined condition operator
+                return;
+            }
+        }
+
+        if let Some(Some(..)) =
+            self.explicit_operator_type(region, &expr.pos, "??", &[Some(actual.clone())])
+        {
+            return;
+        }
+
+        diagnostics.push(Diagnostic::error(
+            &expr.pos,
+            format!(
+                "Expected type '{}', got {}",
+                boolean.designator(),
+                actual.describe()
+            ),
+        ));
+    }
+
+    /// Check that an analyzed name denotes a signal when it is known what it denotes
+    pub fn check_signal_name(
+        &self,
+        pos: &SrcPos,
+        resolved: Option<&ResolvedName>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let ent = if let Some(ResolvedName::Known(NamedEntities::Single(ref ent))) = resolved {
+            ent
+        } else {
+            // Overloaded names are reported as not being a signal
+            if let Some(ResolvedName::Known(NamedEntities::Overloaded(ref overloaded))) = resolved {
+                diagnostics.push(Diagnostic::error(
+                    pos,
+                    format!("Expected signal, got {}", overloaded.first().describe()),
+                ));
+            }
+            return;
+        };
+
+        let is_signal = match ent.actual_kind() {
+            NamedEntityKind::Object(ObjectClass::Signal, ..) => true,
+            NamedEntityKind::InterfaceObject(ref object) => object.class == ObjectClass::Signal,
+            // Object alias with unknown class
+            NamedEntityKind::OtherAlias => true,
+            _ => false,
+        };
+
+        if !is_signal {
+            diagnostics.push(Diagnostic::error(
+                pos,
+                format!("Expected signal, got {}", ent.describe()),
+            ));
+        }
+    }
+
+    /// Check that the prefix of a predefined attribute is of the class the attribute is defined for
+    /// Signal attributes require a signal, type attributes a type and array attributes an array
+    /// object or type
+    pub fn check_attribute_prefix(
+        &self,
+        pos: &SrcPos,
+        resolved: Option<&ResolvedName>,
+        attr: &Ident,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let ent = if let Some(ResolvedName::Known(NamedEntities::Single(ref ent))) = resolved {
+            ent
+        } else {
+            return;
+        };
+
+        let kind = ent.actual_kind();
+        if let NamedEntityKind::OtherAlias = kind {
+            // Object alias with unknown class
+            return;
+        }
+
+        let name = attr.item.name_utf8().to_lowercase();
+        let expected = match name.as_str() {
+            "event" | "active" | "last_event" | "last_active" | "last_value" | "driving"
+            | "driving_value" | "stable" | "quiet" | "transaction" | "delayed" => {
+                let is_signal = match kind {
+                    NamedEntityKind::Object(ObjectClass::Signal, ..) => true,
+                    NamedEntityKind::InterfaceObject(ref object) => {
+                        object.class == ObjectClass::Signal
+                    }
+                    _ => false,
+                };
+
+                if is_signal {
+                    return;
+                }
+                "signal"
+            }
+            "pos" | "val" | "succ" | "pred" | "leftof" | "rightof" | "base" => {
+                if kind.is_type() {
+                    return;
+                }
+                "type"
+            }
+            "length" | "range" | "reverse_range" => {
+                let class = if kind.is_type() {
+                    ent.base_type_class()
+                } else if let Some(subtype) = ent.object_subtype() {
+                    subtype.base().base_type_class()
+                } else {
+                    None
+                };
+
+                match class {
+                    // Access values are implicitly dereferenced
+                    Some(Type::Array(..)) | Some(Type::Access) | None => {
+                        return;
+                    }
+                    Some(_) => "array",
+                }
+            }
+            _ => {
+                return;
+            }
+        };
+
+        diagnostics.push(Diagnostic::error(
+            pos,
+            format!(
+                "Expected {} prefix for attribute '{}', got {}",
+                expected,
+                attr.item,
+                ent.describe()
+            ),
+        ));
+    }
+}
@@ -247,6 +247,9 @@ pub enum Expression {
 
     /// LRM 9.3.7 Allocators
     New(Box<WithPos<Allocator>>),
+
+    /// VHDL-2019 conditional expressions, only parsed as the initial value of a declaration
+    Conditional(Box<Conditionals<WithPos<Expression>>>),
 }
 
 /// An identifier together with the lexical source location it occurs in.
@@ -753,6 +756,26 @@ pub enum AssignmentRightHand<T> {
     Selected(Selection<T>),
 }
 
+impl<T> AssignmentRightHand<T> {
+    /// The items of all alternatives
+    pub fn items(&self) -> Vec<&T> {
+        match self {
+            AssignmentRightHand::Simple(ref item) => vec![item],
+            AssignmentRightHand::Conditional(ref conditionals) => conditionals
+                .conditionals
+                .iter()
+                .map(|conditional| &conditional.item)
+                .chain(conditionals.else_item.iter())
+                .collect(),
+            AssignmentRightHand::Selected(ref selection) => selection
+                .alternatives
+                .iter()
+                .map(|alternative| &alternative.item)
+                .collect(),
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct Conditional<T> {
     pub condition: WithPos<Expression>,
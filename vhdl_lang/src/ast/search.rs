@@ -9,6 +9,7 @@
 #![allow(clippy::unneeded_field_pattern)]
 
 use super::*;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[must_use]
 pub enum SearchResult {
@@ -66,6 +67,12 @@ pub trait Searcher {
     fn search_with_pos(&mut self, _pos: &SrcPos) -> SearchState {
         NotFinished
     }
+
+    /// Search an expression before its sub expressions are searched
+    fn search_expression(&mut self, _pos: &SrcPos, _expr: &Expression) -> SearchState {
+        NotFinished
+    }
+
     fn search_source(&mut self, _source: &Source) -> SearchState {
         NotFinished
     }
@@ -635,6 +642,7 @@ impl Search for TypeDeclaration {
             TypeDefinition::Record(ref element_decls) => {
                 return_if_found!(searcher.search_decl_pos(self.ident.pos()).or_not_found());
                 for elem in element_decls {
+                    return_if_found!(searcher.search_decl_pos(elem.ident.pos()).or_not_found());
                     return_if_found!(elem.subtype.search(searcher));
                 }
             }
@@ -685,6 +693,7 @@ impl Search for TypeDeclaration {
 
 fn search_pos_expr(pos: &SrcPos, expr: &Expression, searcher: &mut impl Searcher) -> SearchResult {
     return_if_found!(searcher.search_with_pos(pos).or_not_found());
+    return_if_found!(searcher.search_expression(pos, expr).or_not_found());
     match expr {
         Expression::Binary(_, ref left, ref right) => {
             return_if_found!(left.search(searcher));
@@ -701,6 +710,9 @@ fn search_pos_expr(pos: &SrcPos, expr: &Expression, searcher: &mut impl Searcher
                 Allocator::Subtype(ref subtype) => subtype.search(searcher),
             }
         }
+        Expression::Conditional(ref conditionals) => {
+            search_conditionals(conditionals, true, searcher)
+        }
         Expression::Literal(_) => NotFound,
     }
 }
@@ -1108,6 +1120,56 @@ impl Searcher for ItemAtCursor {
     }
 }
 
+// Search for the smallest expression containing the cursor
+pub struct ExpressionAtCursor {
+    source: Source,
+    cursor: Position,
+    result: Option<WithPos<Expression>>,
+}
+
+impl ExpressionAtCursor {
+    pub fn new(source: &Source, cursor: Position) -> ExpressionAtCursor {
+        ExpressionAtCursor {
+            source: source.clone(),
+            cursor,
+            result: None,
+        }
+    }
+
+    fn is_inside(&self, pos: &SrcPos) -> bool {
+        pos.start() <= self.cursor && self.cursor <= pos.end()
+    }
+
+    pub fn search(
+        searchable: &impl Search,
+        source: &Source,
+        cursor: Position,
+    ) -> Option<WithPos<Expression>> {
+        let mut searcher = Self::new(source, cursor);
+        let _ = searchable.search(&mut searcher);
+        searcher.result
+    }
+}
+
+impl Searcher for ExpressionAtCursor {
+    fn search_expression(&mut self, pos: &SrcPos, expr: &Expression) -> SearchState {
+        // Sub expressions are searched after the expression itself
+        // so the last expression containing the cursor is the smallest one
+        if self.is_inside(pos) {
+            self.result = Some(WithPos::new(expr.clone(), pos.clone()));
+        }
+        NotFinished
+    }
+
+    fn search_source(&mut self, source: &Source) -> SearchState {
+        if source == &self.source {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+}
+
 // Search for all reference to declaration/defintion
 pub struct FindAllReferences {
     decl_pos: SrcPos,
@@ -1146,3 +1208,121 @@ impl Searcher for FindAllReferences {
         NotFinished
     }
 }
+
+/// Find all references to a declaration including the declaration itself and pass each
+/// of them to a callback when it is found
+/// The search stops as soon as the cancel flag is set, also when set by the callback
+pub struct FindReferencesStreaming<'a> {
+    decl_pos: SrcPos,
+    on_reference: &'a mut dyn FnMut(&SrcPos),
+    cancel: &'a AtomicBool,
+}
+
+impl<'a> FindReferencesStreaming<'a> {
+    pub fn new(
+        decl_pos: &SrcPos,
+        on_reference: &'a mut dyn FnMut(&SrcPos),
+        cancel: &'a AtomicBool,
+    ) -> FindReferencesStreaming<'a> {
+        FindReferencesStreaming {
+            decl_pos: decl_pos.clone(),
+            on_reference,
+            cancel,
+        }
+    }
+
+    pub fn search(
+        searchable: &impl Search,
+        decl_pos: &SrcPos,
+        on_reference: &'a mut dyn FnMut(&SrcPos),
+        cancel: &'a AtomicBool,
+    ) {
+        let mut searcher = Self::new(decl_pos, on_reference, cancel);
+        let _ = searchable.search(&mut searcher);
+    }
+
+    /// Finish the whole search when cancelled
+    fn cancelled(&self) -> SearchState {
+        if self.cancel.load(Ordering::Relaxed) {
+            Finished(Found)
+        } else {
+            NotFinished
+        }
+    }
+}
+
+impl<'a> Searcher for FindReferencesStreaming<'a> {
+    fn search_with_pos(&mut self, _pos: &SrcPos) -> SearchState {
+        self.cancelled()
+    }
+
+    fn search_decl_pos(&mut self, decl_pos: &SrcPos) -> SearchState {
+        if let Finished(result) = self.cancelled() {
+            return Finished(result);
+        }
+        if decl_pos == &self.decl_pos {
+            (self.on_reference)(decl_pos);
+        }
+        self.cancelled()
+    }
+
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &Reference) -> SearchState {
+        if let Finished(result) = self.cancelled() {
+            return Finished(result);
+        }
+        if let Some(ref reference) = reference {
+            if reference == &self.decl_pos {
+                (self.on_reference)(pos);
+            }
+        };
+        self.cancelled()
+    }
+}
+
+/// Declarations and references of all design units
+#[derive(Default, Clone, Debug)]
+pub struct IndexData {
+    /// Positions of declarations
+    pub declarations: Vec<SrcPos>,
+    /// Positions of references together with the position of the referenced declaration
+    pub references: Vec<(SrcPos, SrcPos)>,
+}
+
+impl IndexData {
+    pub fn new(searchable: &impl Search) -> IndexData {
+        let mut index = IndexData::default();
+        let _ = searchable.search(&mut index);
+        index
+    }
+
+    /// Find all references to a declaration including the declaration itself
+    /// without searching the design units again
+    pub fn find_all_references(&self, decl_pos: &SrcPos) -> Vec<SrcPos> {
+        let mut references = Vec::new();
+        for pos in self.declarations.iter() {
+            if pos == decl_pos {
+                references.push(pos.clone());
+            }
+        }
+        for (pos, reference) in self.references.iter() {
+            if reference == decl_pos {
+                references.push(pos.clone());
+            }
+        }
+        references
+    }
+}
+
+impl Searcher for IndexData {
+    fn search_decl_pos(&mut self, decl_pos: &SrcPos) -> SearchState {
+        self.declarations.push(decl_pos.clone());
+        NotFinished
+    }
+
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, reference: &Reference) -> SearchState {
+        if let Some(ref reference) = reference {
+            self.references.push((pos.clone(), reference.clone()));
+        };
+        NotFinished
+    }
+}
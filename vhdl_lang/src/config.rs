@@ -6,6 +6,7 @@
 
 //! Configuration of the design hierarchy and other settings
 
+use crate::analysis::{CasingConvention, DeclarationKind, Lint};
 use crate::data::*;
 use fnv::FnvHashMap;
 use std::env;
@@ -19,6 +20,8 @@ use toml::Value;
 pub struct Config {
     // A map from library name to file name
     libraries: FnvHashMap<String, LibraryConfig>,
+    // Opt-in lints such as the casing conventions of [lint.casing]
+    lints: Vec<Lint>,
 }
 
 #[derive(Clone, PartialEq, Default, Debug)]
@@ -165,7 +168,20 @@ impl Config {
             );
         }
 
-        Ok(Config { libraries })
+        let mut lints = Vec::new();
+        if let Some(casing) = config.get("lint").and_then(|lint| lint.get("casing")) {
+            let casing = casing.as_table().ok_or("lint.casing must be a table")?;
+            for (kind_name, pattern) in casing.iter() {
+                let kind = DeclarationKind::from_name(kind_name)
+                    .ok_or_else(|| format!("unknown declaration kind {}", kind_name))?;
+                let pattern = pattern
+                    .as_str()
+                    .ok_or_else(|| format!("not a string {}", pattern))?;
+                lints.push(Lint::Casing(kind, CasingConvention::from_pattern(pattern)?));
+            }
+        }
+
+        Ok(Config { libraries, lints })
     }
 
     pub fn read_file_path(file_name: &Path) -> io::Result<Config> {
@@ -186,6 +202,10 @@ impl Config {
         self.libraries.values()
     }
 
+    pub fn lints(&self) -> &[Lint] {
+        &self.lints
+    }
+
     /// Append another config to self
     ///
     /// In case of conflict the appended config takes precedence
@@ -208,6 +228,7 @@ impl Config {
                 );
             }
         }
+        self.lints.extend(config.lints.iter().cloned());
     }
 
     /// Load configuration file from installation folder
@@ -310,6 +331,7 @@ fn is_literal(pattern: &str, is_windows: bool) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::Casing;
     use pretty_assertions::assert_eq;
     use tempfile;
 
@@ -389,6 +411,55 @@ lib1.files = [
         assert_eq!(messages, vec![]);
     }
 
+    #[test]
+    fn config_with_casing_lints() {
+        let config = Config::from_str(
+            "
+[libraries]
+
+[lint.casing]
+constant = 'C_{UPPER_SNAKE_CASE}'
+signal = '{lower_snake_case}'
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.lints(),
+            &[
+                Lint::Casing(
+                    DeclarationKind::Constant,
+                    CasingConvention::from_pattern("C_{UPPER_SNAKE_CASE}").unwrap()
+                ),
+                Lint::Casing(DeclarationKind::Signal, Casing::LowerSnakeCase.into()),
+            ]
+        );
+
+        assert_eq!(
+            Config::from_str(
+                "
+[libraries]
+
+[lint.casing]
+port = '{lower_snake_case}'
+",
+                Path::new(""),
+            ),
+            Err("unknown declaration kind port".to_owned())
+        );
+        assert!(Config::from_str(
+            "
+[libraries]
+
+[lint.casing]
+type = 'PascalCase'
+",
+            Path::new(""),
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_append_config() {
         let parent0 = Path::new("parent_folder0");
@@ -102,6 +102,21 @@ impl Diagnostic {
 
 pub type DiagnosticResult<T> = Result<T, Diagnostic>;
 
+/// Sort diagnostics by file name and position with the message as a tie-breaker
+/// such that the order is stable between runs and independent of analysis order
+pub fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by(|a, b| {
+        let a_range = a.pos.range();
+        let b_range = b.pos.range();
+        a.pos
+            .file_name()
+            .cmp(b.pos.file_name())
+            .then_with(|| a_range.start.cmp(&b_range.start))
+            .then_with(|| a_range.end.cmp(&b_range.end))
+            .then_with(|| a.message.cmp(&b.message))
+    });
+}
+
 pub trait DiagnosticHandler {
     fn push(self: &mut Self, diagnostic: Diagnostic);
     fn append(self: &mut Self, diagnostics: Vec<Diagnostic>) {
@@ -13,11 +13,16 @@ mod data;
 mod project;
 mod syntax;
 
+pub use crate::analysis::{
+    AnalysisEvent, BindingInfo, Casing, CasingConvention, ConfigInfo, DeclarationKind, EditImpact,
+    EnumInfo, Lint, MemberUsage, ProcessInfo, RenameConflict, RenamePreview, ResetInfo, Tooltip,
+};
+pub use crate::ast::search::IndexData;
 pub use crate::config::Config;
 pub use crate::data::{
     Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType, Position,
     Range, Severity, Source, SrcPos,
 };
 
-pub use crate::project::{Project, SourceFile};
+pub use crate::project::{Analyzer, Project, SourceFile};
 pub use crate::syntax::{ParserResult, VHDLParser};
@@ -4,14 +4,19 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
-use crate::ast::DesignFile;
+use crate::analysis::{
+    AnalysisEvent, ConfigInfo, DesignRoot, EditImpact, EnumInfo, Lint, MemberUsage, ProcessInfo,
+    RenamePreview, SymbolKind, Tooltip, TypeInfo,
+};
+use crate::ast::search::IndexData;
+use crate::ast::{DesignFile, UnitId};
 use crate::config::Config;
 use crate::data::*;
 use crate::syntax::VHDLParser;
 use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 
 pub struct Project {
     parser: VHDLParser,
@@ -33,6 +38,9 @@ impl Project {
 
     pub fn from_config(config: &Config, messages: &mut dyn MessageHandler) -> Project {
         let mut project = Project::new();
+        for lint in config.lints() {
+            project.enable_lint(lint.clone());
+        }
         let mut files_to_parse: FnvHashMap<PathBuf, FnvHashSet<Symbol>> = FnvHashMap::default();
 
         for library in config.iter_libraries() {
@@ -130,7 +138,28 @@ impl Project {
             .insert(source.file_name().to_owned(), source_file);
     }
 
-    pub fn analyse(&mut self) -> Vec<Diagnostic> {
+    /// Use the sources instead of the std library of the configuration
+    /// The sources are not part of the project and are not updated by update_source
+    pub fn add_standard_library_from(
+        &mut self,
+        sources: &[Source],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let std_sym = self.parser.symbol(&Latin1String::new(b"std"));
+        self.files.retain(|_, source_file| {
+            !(source_file.library_names.remove(&std_sym) && source_file.library_names.is_empty())
+        });
+        self.empty_libraries.remove(&std_sym);
+        self.root.add_standard_library_from(sources, diagnostics);
+    }
+
+    /// Enable an opt-in lint which is not checked by default
+    pub fn enable_lint(&mut self, lint: Lint) {
+        self.root.enable_lint(lint);
+    }
+
+    /// Add parsed design files to the design root and return the parser diagnostics
+    fn add_design_files(&mut self) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         for source_file in self.files.values_mut() {
@@ -140,6 +169,8 @@ impl Project {
 
             for library_name in source_file.library_names.iter() {
                 let design_file = design_files.pop().unwrap();
+                self.root
+                    .add_source(library_name.clone(), &source_file.source);
                 self.root.add_design_file(library_name.clone(), design_file);
             }
 
@@ -152,10 +183,43 @@ impl Project {
             self.root.ensure_library(library_name.clone());
         }
 
+        diagnostics
+    }
+
+    pub fn analyse(&mut self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.add_design_files();
         self.root.analyze(&mut diagnostics);
         diagnostics
     }
 
+    /// Analyse and report the changes of the analysis state of design units as events
+    pub fn analyse_with_events(
+        &mut self,
+        events: &mut dyn FnMut(AnalysisEvent),
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = self.add_design_files();
+        self.root.analyze_with_events(&mut diagnostics, events);
+        diagnostics
+    }
+
+    /// Analyse and return only the diagnostics of the changed sources and of the sources
+    /// of design units depending on them
+    pub fn analyse_changed(&mut self, changed_sources: &[Source]) -> Vec<Diagnostic> {
+        let mut diagnostics = self.add_design_files();
+        diagnostics.retain(|diagnostic| changed_sources.contains(&diagnostic.pos.source));
+        diagnostics.extend(self.root.analyze_changed(changed_sources));
+        diagnostics
+    }
+
+    /// Analyse and also return an index of all declarations and references
+    /// Each design unit is indexed as part of its analysis
+    pub fn analyse_and_index(&mut self) -> (Vec<Diagnostic>, IndexData) {
+        let mut diagnostics = self.add_design_files();
+        let (analysis_diagnostics, index) = self.root.analyze_and_index();
+        diagnostics.extend(analysis_diagnostics);
+        (diagnostics, index)
+    }
+
     /// Search for reference at position
     /// Character offset on a line in a document (zero-based). Assuming that the line is
     /// represented as a string, the `character` value represents the gap between the
@@ -171,6 +235,96 @@ impl Project {
         self.root.find_all_references(decl_pos)
     }
 
+    pub fn find_references_streaming(
+        &self,
+        decl_pos: &SrcPos,
+        on_reference: &mut dyn FnMut(&SrcPos),
+        cancel: &AtomicBool,
+    ) {
+        self.root
+            .find_references_streaming(decl_pos, on_reference, cancel)
+    }
+
+    pub fn rename(&self, decl_pos: &SrcPos) -> Vec<SrcPos> {
+        self.root.rename(decl_pos)
+    }
+
+    pub fn rename_preview(&self, decl_pos: &SrcPos, new_name: &str) -> RenamePreview {
+        self.root.rename_preview(decl_pos, new_name)
+    }
+
+    pub fn task_comments(&self, patterns: &[&str]) -> Vec<(SrcPos, String)> {
+        self.root.task_comments(patterns)
+    }
+
+    pub fn reference_kind(&self, source: &Source, cursor: Position) -> Option<SymbolKind> {
+        self.root.reference_kind(source, cursor)
+    }
+
+    pub fn cross_references(&self) -> Vec<(SrcPos, SrcPos, SymbolKind)> {
+        self.root.cross_references()
+    }
+
+    pub fn tooltip(&self, source: &Source, cursor: Position) -> Option<Tooltip> {
+        self.root.tooltip(source, cursor)
+    }
+
+    pub fn expression_at(&self, source: &Source, cursor: Position) -> Option<(SrcPos, TypeInfo)> {
+        self.root.expression_at(source, cursor)
+    }
+
+    pub fn name_attribute_value(&self, source: &Source, cursor: Position) -> Option<String> {
+        self.root.name_attribute_value(source, cursor)
+    }
+
+    pub fn package_api(&self, pkg: &UnitId) -> Vec<MemberUsage> {
+        self.root.package_api(pkg)
+    }
+
+    pub fn required_use_clauses(&self, unit_id: &UnitId) -> Vec<String> {
+        self.root.required_use_clauses(unit_id)
+    }
+
+    pub fn enum_types(&self) -> Vec<EnumInfo> {
+        self.root.enum_types()
+    }
+
+    pub fn entity_of_architecture(&self, arch: &UnitId) -> Option<UnitId> {
+        self.root.entity_of_architecture(arch)
+    }
+
+    pub fn package_body_of(&self, pkg: &UnitId) -> Option<UnitId> {
+        self.root.package_body_of(pkg)
+    }
+
+    pub fn package_decl_of(&self, body: &UnitId) -> Option<UnitId> {
+        self.root.package_decl_of(body)
+    }
+
+    pub fn units_in_source(&self, source: &Source) -> Vec<UnitId> {
+        self.root.units_in_source(source)
+    }
+
+    pub fn dependents_of(&self, unit_id: &UnitId) -> FnvHashSet<UnitId> {
+        self.root.dependents_of(unit_id)
+    }
+
+    pub fn edit_impact(&self, source: &Source) -> EditImpact {
+        self.root.edit_impact(source)
+    }
+
+    pub fn architectures_of_entity(&self, entity: &UnitId) -> Vec<UnitId> {
+        self.root.architectures_of_entity(entity)
+    }
+
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        self.root.processes()
+    }
+
+    pub fn configurations(&self) -> Vec<ConfigInfo> {
+        self.root.configurations()
+    }
+
     pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
         self.files.values()
     }
@@ -199,6 +353,56 @@ impl Default for Project {
     }
 }
 
+/// Analyzes several projects in sequence while reusing one thread pool
+///
+/// Only the thread pool is reused. Each project is parsed and analyzed from scratch,
+/// including the standard and ieee libraries of its configuration, since the
+/// symbols of the analyzed design units belong to the project
+pub struct Analyzer {
+    pool: rayon::ThreadPool,
+}
+
+impl Analyzer {
+    pub fn new() -> Analyzer {
+        // Zero lets rayon choose the number of threads
+        Analyzer::with_num_threads(0)
+    }
+
+    pub fn with_num_threads(num_threads: usize) -> Analyzer {
+        Analyzer {
+            pool: rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .expect("Failed to create thread pool"),
+        }
+    }
+
+    /// Parse and analyze the project of the configuration within the thread pool
+    /// The libraries of the configuration are parsed and analyzed again on each call
+    pub fn analyze_project(
+        &self,
+        config: &Config,
+        messages: &mut dyn MessageHandler,
+    ) -> Vec<Diagnostic> {
+        let (diagnostics, project_messages) = self.pool.install(|| {
+            let mut project_messages = Vec::new();
+            let mut project = Project::from_config(config, &mut project_messages);
+            (project.analyse(), project_messages)
+        });
+
+        for message in project_messages {
+            messages.push(message);
+        }
+        diagnostics
+    }
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct SourceFile {
     library_names: FnvHashSet<Symbol>,
     source: Source,
@@ -252,6 +456,53 @@ lib.files = ['file.vhd']
         check_no_diagnostics(&project.analyse());
     }
 
+    #[test]
+    fn task_comments_of_files_without_design_units() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path1 = root.path().join("file1.vhd");
+        std::fs::write(
+            &vhdl_file_path1,
+            "
+-- TODO in design unit
+entity ent is
+end entity;
+        ",
+        )
+        .unwrap();
+        let vhdl_file_path2 = root.path().join("file2.vhd");
+        std::fs::write(
+            &vhdl_file_path2,
+            "
+-- TODO without design unit
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file1.vhd', 'file2.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(&config, &mut messages);
+        assert_eq!(messages, vec![]);
+        check_no_diagnostics(&project.analyse());
+
+        let texts: Vec<String> = project
+            .task_comments(&["TODO"])
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "TODO in design unit".to_owned(),
+                "TODO without design unit".to_owned()
+            ]
+        );
+    }
+
     /// Test that the same file can be added to several libraries
     #[test]
     fn test_same_file_in_multiple_libraries() {
@@ -299,6 +550,53 @@ use_lib.files = ['use_file.vhd']
         check_no_diagnostics(&project.analyse());
     }
 
+    #[test]
+    fn analyzer_is_reused_for_several_projects() {
+        let analyzer = Analyzer::with_num_threads(2);
+
+        let root1 = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root1.path().join("file.vhd"),
+            "
+entity ent is
+end entity;
+        ",
+        )
+        .unwrap();
+
+        let root2 = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root2.path().join("file.vhd"),
+            "
+architecture a of missing is
+begin
+end architecture;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+        ";
+
+        let config1 = Config::from_str(config_str, root1.path()).unwrap();
+        let config2 = Config::from_str(config_str, root2.path()).unwrap();
+        let mut messages = Vec::new();
+
+        check_no_diagnostics(&analyzer.analyze_project(&config1, &mut messages));
+
+        let diagnostics = analyzer.analyze_project(&config2, &mut messages);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "No entity 'missing' within library 'lib'"
+        );
+
+        check_no_diagnostics(&analyzer.analyze_project(&config1, &mut messages));
+        assert_eq!(messages, vec![]);
+    }
+
     fn update(project: &mut Project, source: &mut Source, contents: &str) {
         std::fs::write(&std::path::Path::new(source.file_name()), contents).unwrap();
         *source = Source::from_latin1_file(source.file_name()).unwrap();
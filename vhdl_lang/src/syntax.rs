@@ -31,5 +31,5 @@ mod waveform;
 #[cfg(test)]
 pub mod test;
 
-pub use parser::{ParserResult, VHDLParser};
+pub use parser::{source_comments, ParserResult, VHDLParser};
 pub use tokens::Symbols;
@@ -6,7 +6,9 @@
 
 use super::common::error_on_end_identifier_mismatch;
 use super::common::ParseResult;
-use super::declarative_part::{is_declarative_part, parse_declarative_part};
+use super::declarative_part::{
+    is_declarative_part, parse_declarative_part, recover_misplaced_declarations,
+};
 use super::expression::parse_aggregate_leftpar_known;
 use super::expression::{parse_choices, parse_expression};
 use super::interface_declaration::{parse_generic_interface_list, parse_port_interface_list};
@@ -637,6 +639,7 @@ pub fn parse_labeled_concurrent_statements_end_token(
 ) -> ParseResult<(Vec<LabeledConcurrentStatement>, Token)> {
     let mut statements = Vec::new();
     loop {
+        recover_misplaced_declarations(stream, diagnostics)?;
         let token = stream.expect()?;
         match token.kind {
             End | Elsif | Else | When => {
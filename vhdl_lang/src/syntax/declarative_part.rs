@@ -16,7 +16,7 @@ use super::subprogram::parse_subprogram;
 use super::tokens::{Kind::*, *};
 use super::type_declaration::parse_type_declaration;
 use crate::ast::{ContextClause, Declaration, PackageInstantiation};
-use crate::data::DiagnosticHandler;
+use crate::data::{Diagnostic, DiagnosticHandler};
 
 pub fn parse_package_instantiation(stream: &mut TokenStream) -> ParseResult<PackageInstantiation> {
     stream.expect_kind(Package)?;
@@ -78,6 +78,42 @@ pub fn parse_declarative_part(
 pub fn parse_declarative_part_leave_end_token(
     stream: &mut TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<Vec<Declaration>> {
+    parse_declarations(stream, diagnostics, false)
+}
+
+/// Declarations which cannot be confused with the start of a statement
+fn is_misplaced_declaration_token(kind: Kind) -> bool {
+    match kind {
+        Type | Subtype | Shared | Constant | Signal | Variable | File | Attribute | Alias
+        | Impure | Function | Procedure | Package | Use => true,
+        _ => false,
+    }
+}
+
+/// Recover from declarations placed after 'begin' among the statements
+/// The declarations are parsed to continue with the statements after them but are not kept
+pub fn recover_misplaced_declarations(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<()> {
+    if let Some(token) = stream.peek()? {
+        if is_misplaced_declaration_token(token.kind) {
+            diagnostics.push(Diagnostic::error(
+                &token,
+                "Declarations are not allowed after 'begin', they must precede 'begin'",
+            ));
+            parse_declarations(stream, diagnostics, true)?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_declarations(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+    // Stop at the first token which is not a declaration
+    among_statements: bool,
 ) -> ParseResult<Vec<Declaration>> {
     let mut declarations: Vec<Declaration> = Vec::new();
 
@@ -143,6 +179,7 @@ pub fn parse_declarative_part_leave_end_token(
                 }
             }
 
+            _ if among_statements => break,
             _ => {
                 diagnostics.push(token.kinds_error(&[
                     Type, Subtype, Component, Impure, Function, Procedure, Package, For, File,
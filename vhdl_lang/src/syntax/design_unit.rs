@@ -576,6 +576,69 @@ end;
         );
     }
 
+    #[test]
+    fn error_on_signal_declaration_after_begin() {
+        let (code, design_file, diagnostics) = parse_str(
+            "
+architecture arch_name of myent is
+begin
+  signal foo : natural;
+  foo_proc : process
+  begin
+    variable bar : natural;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    code.s1("signal"),
+                    "Declarations are not allowed after 'begin', they must precede 'begin'",
+                ),
+                Diagnostic::error(
+                    code.s1("variable"),
+                    "Declarations are not allowed after 'begin', they must precede 'begin'",
+                ),
+            ],
+        );
+
+        match design_file.design_units.as_slice() {
+            [AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch))] => {
+                assert_eq!(arch.decl.len(), 0);
+                assert_eq!(arch.statements.len(), 1);
+            }
+            _ => panic!("Expected architecture"),
+        }
+    }
+
+    #[test]
+    fn signal_declaration_before_begin() {
+        let (_, design_file) = parse_ok(
+            "
+architecture arch_name of myent is
+  signal foo : natural;
+begin
+  foo_proc : process
+    variable bar : natural;
+  begin
+    wait;
+  end process;
+end architecture;
+",
+        );
+
+        match design_file.design_units.as_slice() {
+            [AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch))] => {
+                assert_eq!(arch.decl.len(), 1);
+                assert_eq!(arch.statements.len(), 1);
+            }
+            _ => panic!("Expected architecture"),
+        }
+    }
+
     #[test]
     fn test_package_declaration() {
         let code = Code::new(
@@ -53,8 +53,8 @@ fn kind_to_binary_op(kind: Kind) -> Option<(Binary, usize)> {
         Plus => Some((Binary::Plus, 5)),
         Minus => Some((Binary::Minus, 5)),
         Concat => Some((Binary::Concat, 5)),
-        Times => Some((Binary::Times, 5)),
 
+        Times => Some((Binary::Times, 7)),
         Div => Some((Binary::Div, 7)),
         Mod => Some((Binary::Mod, 7)),
         Rem => Some((Binary::Rem, 7)),
@@ -421,6 +421,43 @@ pub fn parse_expression_initial_token(
     parse_expr_initial_token(stream, token, 0)
 }
 
+/// VHDL-2019 conditional expression
+/// expression when condition { else expression when condition } [ else expression ]
+/// A plain expression is returned when there are no conditions
+pub fn parse_conditional_expression(stream: &mut TokenStream) -> ParseResult<WithPos<Expression>> {
+    let item = parse_expression(stream)?;
+    if !stream.skip_if_kind(When)? {
+        return Ok(item);
+    }
+
+    let start_pos = item.pos.clone();
+    let condition = parse_expression(stream)?;
+    let mut end_pos = condition.pos.clone();
+    let mut conditionals = vec![Conditional { condition, item }];
+    let mut else_item = None;
+
+    while stream.skip_if_kind(Else)? {
+        let item = parse_expression(stream)?;
+        if stream.skip_if_kind(When)? {
+            let condition = parse_expression(stream)?;
+            end_pos = condition.pos.clone();
+            conditionals.push(Conditional { condition, item });
+        } else {
+            end_pos = item.pos.clone();
+            else_item = Some(item);
+            break;
+        }
+    }
+
+    Ok(WithPos::new(
+        Expression::Conditional(Box::new(Conditionals {
+            conditionals,
+            else_item,
+        })),
+        start_pos.combine_into(&end_pos),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1165,4 +1202,20 @@ mod tests {
 
         assert_expression_is("and 1 + 2", "((And Integer(1)) Plus Integer(2))");
     }
+
+    #[test]
+    fn parses_multiplication_with_precedence_of_multiplying_operators() {
+        assert_expression_is("1+2*3", "(Integer(1) Plus (Integer(2) Times Integer(3)))");
+
+        assert_expression_is("1*2-3", "((Integer(1) Times Integer(2)) Minus Integer(3))");
+
+        assert_expression_is("1*2/3", "((Integer(1) Times Integer(2)) Div Integer(3))");
+
+        assert_expression_is("1/2*3", "((Integer(1) Div Integer(2)) Times Integer(3))");
+
+        assert_expression_is(
+            "1 & 2 * 3",
+            "(Integer(1) Concat (Integer(2) Times Integer(3)))",
+        );
+    }
 }
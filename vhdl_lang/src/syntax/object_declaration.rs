@@ -5,7 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::common::ParseResult;
-use super::expression::parse_expression;
+use super::expression::{parse_conditional_expression, parse_expression};
 use super::names::parse_identifier_list;
 use super::subtype_indication::parse_subtype_indication;
 use super::tokens::{Kind::*, TokenStream};
@@ -17,7 +17,7 @@ pub fn parse_optional_assignment(
     stream: &mut TokenStream,
 ) -> ParseResult<Option<WithPos<Expression>>> {
     if stream.pop_if_kind(ColonEq)?.is_some() {
-        let expr = parse_expression(stream)?;
+        let expr = parse_conditional_expression(stream)?;
         Ok(Some(expr))
     } else {
         Ok(None)
@@ -218,6 +218,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_conditional_expression() {
+        let code = Code::new("constant foo : natural := 0 when cond else 1;");
+        assert_eq!(
+            code.with_stream(parse_object_declaration),
+            vec![ObjectDeclaration {
+                class: ObjectClass::Constant,
+                ident: code.s1("foo").ident(),
+                subtype_indication: code.s1("natural").subtype_indication(),
+                expression: Some(WithPos::new(
+                    Expression::Conditional(Box::new(Conditionals {
+                        conditionals: vec![Conditional {
+                            condition: code.s1("cond").expr(),
+                            item: code.s1("0").expr(),
+                        }],
+                        else_item: Some(code.s1("1").expr()),
+                    })),
+                    code.s1("0 when cond else 1").pos()
+                ))
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_conditional_expression_without_else() {
+        let code = Code::new("constant foo : natural := 0 when cond;");
+        assert_eq!(
+            code.with_stream(parse_object_declaration),
+            vec![ObjectDeclaration {
+                class: ObjectClass::Constant,
+                ident: code.s1("foo").ident(),
+                subtype_indication: code.s1("natural").subtype_indication(),
+                expression: Some(WithPos::new(
+                    Expression::Conditional(Box::new(Conditionals {
+                        conditionals: vec![Conditional {
+                            condition: code.s1("cond").expr(),
+                            item: code.s1("0").expr(),
+                        }],
+                        else_item: None,
+                    })),
+                    code.s1("0 when cond").pos()
+                ))
+            }]
+        );
+    }
+
     #[test]
     fn parses_identifier_list() {
         let code = Code::new("constant foo, bar : natural := 0;");
@@ -51,3 +51,32 @@ impl VHDLParser {
         Ok((source, design_file))
     }
 }
+
+/// The position and text of all comments of a source in the order they appear
+/// Comments after a token which cannot be tokenized are not included
+pub fn source_comments(symbols: &Symbols, source: &Source) -> Vec<(SrcPos, String)> {
+    let contents = source.contents();
+    let mut tokenizer = Tokenizer::new(symbols, source, ContentReader::new(&contents));
+    let mut comments = Vec::new();
+
+    while let Ok(Some(token)) = tokenizer.pop() {
+        if let Some(token_comments) = token.comments {
+            comments.extend(token_comments.leading);
+            comments.extend(token_comments.trailing);
+        }
+    }
+
+    if let Some(final_comments) = tokenizer.get_final_comments() {
+        comments.extend(final_comments);
+    }
+
+    comments
+        .into_iter()
+        .map(|comment| {
+            (
+                source.pos(comment.range.start, comment.range.end),
+                comment.value,
+            )
+        })
+        .collect()
+}
@@ -6,6 +6,7 @@
 
 use super::common::parse_optional;
 use super::common::ParseResult;
+use super::declarative_part::recover_misplaced_declarations;
 use super::expression::{parse_aggregate_leftpar_known, parse_choices, parse_expression};
 use super::names::{parse_name, parse_name_initial_token};
 use super::range::parse_discrete_range;
@@ -68,6 +69,7 @@ pub fn parse_labeled_sequential_statements(
 ) -> ParseResult<(Vec<LabeledSequentialStatement>, Token)> {
     let mut statements = Vec::new();
     loop {
+        recover_misplaced_declarations(stream, diagnostics)?;
         let token = stream.expect()?;
         match token.kind {
             End | Else | Elsif | When => {
@@ -1621,7 +1621,6 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_final_comments(&self) -> Option<Vec<Comment>> {
         self.final_comments.clone()
     }